@@ -2,13 +2,12 @@ extern crate gql_introspector;
 
 use gql_introspector::GQLIntrospector;
 
+/// Reads the endpoint and credentials from `GQL_ENDPOINT`, `GQL_AUTH_TOKEN` and `GQL_USER_AGENT`.
 fn main() {
-    let introspector = GQLIntrospector::new();
-    
+    let introspector = GQLIntrospector::from_env().expect("Failed to read configuration from environment");
+
     introspector
-        .add("Authorization", "Bearer <TOKEN>")
-        .add("User-Agent", "Awesome-Octocat-App")
-        .get_schema("https://api.github.com/graphql")
+        .introspect()
         .expect("Failed to get schema")
         .build()
         .expect("Failed to build schema")
@@ -16,4 +15,4 @@ fn main() {
         .expect("Failed to write schema to file");
 
     println!("Schema introspection and write completed.");
-}
\ No newline at end of file
+}
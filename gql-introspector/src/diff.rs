@@ -0,0 +1,402 @@
+use std::fmt;
+
+use serde_json::json;
+
+use crate::{Field, GQLIntrospector, IntrospectionResult, Type};
+
+/// The differences between two versions of a schema.
+///
+/// Entries name a type (`User`), a field or input field (`User.email`), an argument
+/// (`User.posts(first:)`) or an enum value (`Color.BLUE`). Changed entries describe the
+/// change, e.g. `User.name: String -> String!`. Built-in introspection types are ignored.
+///
+/// It displays as one change per line, prefixed with `+` (added), `-` (removed)
+/// or `~` (changed).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    pub added: Vec<Change>,
+    pub removed: Vec<Change>,
+    pub changed: Vec<Change>,
+}
+
+/// One entry of a `SchemaDiff`, with what it refers to and how it affects clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// The entry, e.g. `User.email` or `User.name: String -> String!`.
+    pub entry: String,
+    pub member: Member,
+    pub severity: Severity,
+}
+
+/// The part of the schema a change refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Member {
+    Type,
+    /// A field of an object or interface type.
+    OutputField,
+    /// A field of an input object type.
+    InputField,
+    Argument,
+    EnumValue,
+}
+
+/// How a change affects existing clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Existing operations keep working.
+    Safe,
+    /// Removes something that was deprecated, so clients were warned.
+    Warning,
+    /// Existing operations may fail, or receive values they do not expect.
+    Breaking,
+}
+
+impl Severity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Safe => "safe",
+            Severity::Warning => "warning",
+            Severity::Breaking => "breaking",
+        }
+    }
+}
+
+impl Member {
+    fn as_str(self) -> &'static str {
+        match self {
+            Member::Type => "type",
+            Member::OutputField => "field",
+            Member::InputField => "input_field",
+            Member::Argument => "argument",
+            Member::EnumValue => "enum_value",
+        }
+    }
+}
+
+impl Change {
+    fn new(entry: String, member: Member, severity: Severity) -> Self {
+        Self { entry, member, severity }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.entry)
+    }
+}
+
+impl PartialEq<&str> for Change {
+    fn eq(&self, other: &&str) -> bool {
+        self.entry == *other
+    }
+}
+
+impl SchemaDiff {
+    /// Compares two introspection results.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The previous version of the schema.
+    /// * `new` - The current version of the schema.
+    pub fn between(old: &IntrospectionResult, new: &IntrospectionResult) -> Self {
+        let mut diff = SchemaDiff::default();
+        let old_types = user_types(old);
+        let new_types = user_types(new);
+
+        for (name, new_type) in &new_types {
+            match old_types.iter().find(|(old_name, _)| old_name == name) {
+                Some((_, old_type)) => diff.compare_types(name, old_type, new_type),
+                None => diff.added.push(Change::new(name.to_string(), Member::Type, Severity::Safe)),
+            }
+        }
+        for (name, _) in &old_types {
+            if !new_types.iter().any(|(new_name, _)| new_name == name) {
+                diff.removed.push(Change::new(name.to_string(), Member::Type, Severity::Breaking));
+            }
+        }
+
+        diff
+    }
+
+    /// Returns `true` if the schemas are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Returns `true` if any change may break existing clients.
+    pub fn is_breaking(&self) -> bool {
+        self.added.iter().chain(&self.removed).chain(&self.changed).any(|change| change.severity == Severity::Breaking)
+    }
+
+    /// Serializes the diff as a JSON array of change records for automation such as CI.
+    ///
+    /// Each record has the owning `type`, the `kind` of change (`added`, `removed` or
+    /// `changed`), the `member` it refers to, the entry as `detail`, and its `severity`
+    /// (`safe`, `warning` or `breaking`), with `breaking` set for the latter.
+    pub fn to_json(&self) -> String {
+        let records = [("added", &self.added), ("removed", &self.removed), ("changed", &self.changed)];
+        let records: Vec<_> = records
+            .into_iter()
+            .flat_map(|(kind, changes)| changes.iter().map(move |change| (kind, change)))
+            .map(|(kind, change)| {
+                json!({
+                    "type": change.entry.split(['.', ':']).next().unwrap_or_default(),
+                    "kind": kind,
+                    "member": change.member.as_str(),
+                    "breaking": change.severity == Severity::Breaking,
+                    "severity": change.severity.as_str(),
+                    "detail": change.entry,
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&records).unwrap_or_default()
+    }
+
+    fn compare_types(&mut self, name: &str, old: &Type, new: &Type) {
+        if old.kind != new.kind {
+            self.changed.push(Change::new(
+                format!("{}: {} -> {}", name, old.kind.as_deref().unwrap_or("UNKNOWN"), new.kind.as_deref().unwrap_or("UNKNOWN")),
+                Member::Type,
+                Severity::Breaking,
+            ));
+            return;
+        }
+
+        let member = if new.is_input_object() { Member::InputField } else { Member::OutputField };
+        self.compare_fields(name, member, &fields(old), &fields(new));
+
+        let values = |t: &Type| -> Vec<(String, bool)> {
+            t.enum_values.iter().flatten().filter_map(|value| Some((value.name.clone()?, value.is_deprecated.unwrap_or(false)))).collect()
+        };
+        let (old_values, new_values) = (values(old), values(new));
+        for (value, _) in &new_values {
+            if !old_values.iter().any(|(old_value, _)| old_value == value) {
+                self.added.push(Change::new(format!("{}.{}", name, value), Member::EnumValue, Severity::Safe));
+            }
+        }
+        for (value, deprecated) in &old_values {
+            if !new_values.iter().any(|(new_value, _)| new_value == value) {
+                self.removed.push(Change::new(format!("{}.{}", name, value), Member::EnumValue, removal_severity(*deprecated)));
+            }
+        }
+    }
+
+    /// Compares the fields, input fields or arguments owned by `owner`, e.g. `User` or
+    /// `User.posts` for arguments.
+    fn compare_fields(&mut self, owner: &str, member: Member, old_fields: &[&Field], new_fields: &[&Field]) {
+        let path = |name: &str| match member {
+            Member::Argument => format!("{}({}:)", owner, name),
+            _ => format!("{}.{}", owner, name),
+        };
+        let is_input = member != Member::OutputField;
+
+        for new_field in new_fields {
+            let Some(name) = new_field.name.as_deref() else { continue };
+            let Some(old_field) = named(old_fields, name) else {
+                // Operations omitting a new required input no longer validate.
+                let required = is_input && is_non_null(new_field.field_type.as_ref()) && new_field.default_value.is_none();
+                let severity = if required { Severity::Breaking } else { Severity::Safe };
+                self.added.push(Change::new(path(name), member, severity));
+                continue;
+            };
+
+            let (old_type, new_type) = (old_field.field_type.as_ref(), new_field.field_type.as_ref());
+            let (old_signature, new_signature) = (type_signature(old_type), type_signature(new_type));
+            if old_signature != new_signature {
+                // Outputs may only get stricter, and inputs only more lenient.
+                let safe = match (old_type, new_type) {
+                    (Some(old_type), Some(new_type)) if is_input => is_subtype(old_type, new_type),
+                    (Some(old_type), Some(new_type)) => is_subtype(new_type, old_type),
+                    _ => false,
+                };
+                let severity = if safe { Severity::Safe } else { Severity::Breaking };
+                self.changed.push(Change::new(format!("{}: {} -> {}", path(name), old_signature, new_signature), member, severity));
+            }
+            if member == Member::OutputField {
+                let (old_args, new_args) = (old_field.args.iter().flatten().collect::<Vec<_>>(), new_field.args.iter().flatten().collect::<Vec<_>>());
+                self.compare_fields(&format!("{}.{}", owner, name), Member::Argument, &old_args, &new_args);
+            }
+        }
+        for old_field in old_fields {
+            let Some(name) = old_field.name.as_deref() else { continue };
+            if named(new_fields, name).is_none() {
+                self.removed.push(Change::new(path(name), member, removal_severity(old_field.is_deprecated.unwrap_or(false))));
+            }
+        }
+    }
+}
+
+/// Removing a deprecated member only warns, since clients were told to stop using it.
+fn removal_severity(deprecated: bool) -> Severity {
+    if deprecated {
+        Severity::Warning
+    } else {
+        Severity::Breaking
+    }
+}
+
+fn fields(t: &Type) -> Vec<&Field> {
+    t.fields.iter().chain(t.input_fields.iter()).flatten().collect()
+}
+
+fn named<'a>(fields: &[&'a Field], name: &str) -> Option<&'a Field> {
+    fields.iter().find(|field| field.name.as_deref() == Some(name)).copied()
+}
+
+fn type_signature(t: Option<&Type>) -> String {
+    t.map(GQLIntrospector::format_type).unwrap_or_default()
+}
+
+fn is_non_null(t: Option<&Type>) -> bool {
+    t.is_some_and(|t| t.kind.as_deref() == Some("NON_NULL"))
+}
+
+/// Returns `true` if every value of type `narrow` is also a value of type `wide`, e.g.
+/// `String!` of `String`, or `[Int!]` of `[Int]`.
+fn is_subtype(narrow: &Type, wide: &Type) -> bool {
+    match (narrow.kind.as_deref(), wide.kind.as_deref()) {
+        (Some("NON_NULL"), Some("NON_NULL")) | (Some("LIST"), Some("LIST")) => match (&narrow.of_type, &wide.of_type) {
+            (Some(narrow), Some(wide)) => is_subtype(narrow, wide),
+            _ => false,
+        },
+        (Some("NON_NULL"), _) => narrow.of_type.as_ref().is_some_and(|narrow| is_subtype(narrow, wide)),
+        (Some("LIST"), _) | (_, Some("LIST" | "NON_NULL")) => false,
+        _ => narrow.name == wide.name,
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for added in &self.added {
+            writeln!(f, "+ {}", added)?;
+        }
+        for removed in &self.removed {
+            writeln!(f, "- {}", removed)?;
+        }
+        for changed in &self.changed {
+            writeln!(f, "~ {}", changed)?;
+        }
+        Ok(())
+    }
+}
+
+fn user_types(introspection_result: &IntrospectionResult) -> Vec<(&str, &Type)> {
+    introspection_result
+        .schema
+        .types
+        .iter()
+        .filter_map(|t| Some((t.name.as_deref()?, t)))
+        .filter(|(name, _)| !name.starts_with("__"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(types: serde_json::Value) -> IntrospectionResult {
+        serde_json::from_value(json!({ "__schema": { "types": types } })).unwrap()
+    }
+
+    #[test]
+    fn test_between() {
+        let string = json!({ "kind": "SCALAR", "name": "String" });
+        let non_null_string = json!({ "kind": "NON_NULL", "ofType": string });
+        let int = json!({ "kind": "SCALAR", "name": "Int" });
+        let non_null_int = json!({ "kind": "NON_NULL", "ofType": int });
+        let old = schema(json!([
+            { "kind": "OBJECT", "name": "User", "fields": [
+                { "name": "name", "type": string },
+                { "name": "age", "type": string },
+                { "name": "title", "type": non_null_string },
+                { "name": "nick", "type": string, "isDeprecated": true },
+                { "name": "posts", "type": int, "args": [{ "name": "first", "type": non_null_int }] }
+            ] },
+            { "kind": "INPUT_OBJECT", "name": "Filter", "inputFields": [
+                { "name": "query", "type": non_null_string },
+                { "name": "tag", "type": string }
+            ] },
+            { "kind": "ENUM", "name": "Color", "enumValues": [{ "name": "RED" }, { "name": "GREEN", "isDeprecated": true }] },
+            { "kind": "OBJECT", "name": "Legacy", "fields": [] }
+        ]));
+        let new = schema(json!([
+            { "kind": "OBJECT", "name": "User", "fields": [
+                { "name": "name", "type": non_null_string },
+                { "name": "email", "type": string },
+                { "name": "title", "type": string },
+                { "name": "posts", "type": int, "args": [
+                    { "name": "first", "type": int },
+                    { "name": "after", "type": non_null_string },
+                    { "name": "limit", "type": non_null_int, "defaultValue": "10" }
+                ] }
+            ] },
+            { "kind": "INPUT_OBJECT", "name": "Filter", "inputFields": [
+                { "name": "query", "type": string },
+                { "name": "tag", "type": non_null_string },
+                { "name": "page", "type": non_null_int },
+                { "name": "size", "type": int }
+            ] },
+            { "kind": "ENUM", "name": "Color", "enumValues": [{ "name": "RED" }, { "name": "BLUE" }] },
+            { "kind": "SCALAR", "name": "Date" },
+            { "kind": "OBJECT", "name": "__Type", "fields": [] }
+        ]));
+
+        let diff = SchemaDiff::between(&old, &new);
+        assert_eq!(
+            diff.added,
+            vec!["User.email", "User.posts(after:)", "User.posts(limit:)", "Filter.page", "Filter.size", "Color.BLUE", "Date"]
+        );
+        assert_eq!(diff.removed, vec!["User.age", "User.nick", "Color.GREEN", "Legacy"]);
+        assert_eq!(
+            diff.changed,
+            vec![
+                "User.name: String -> String!",
+                "User.title: String! -> String",
+                "User.posts(first:): Int! -> Int",
+                "Filter.query: String! -> String",
+                "Filter.tag: String -> String!",
+            ]
+        );
+        assert!(diff.to_string().starts_with("+ User.email\n+ User.posts(after:)\n"));
+        assert!(diff.to_string().ends_with("- Legacy\n~ User.name: String -> String!\n~ User.title: String! -> String\n~ User.posts(first:): Int! -> Int\n~ Filter.query: String! -> String\n~ Filter.tag: String -> String!\n"));
+        assert!(SchemaDiff::between(&new, &new).is_empty());
+        assert!(diff.is_breaking());
+
+        let severity = |changes: &[Change], entry: &str| changes.iter().find(|change| change.entry == entry).unwrap().severity;
+        // Outputs may become stricter, but not more lenient.
+        assert_eq!(severity(&diff.changed, "User.name: String -> String!"), Severity::Safe);
+        assert_eq!(severity(&diff.changed, "User.title: String! -> String"), Severity::Breaking);
+        // Inputs may become more lenient, but not stricter.
+        assert_eq!(severity(&diff.changed, "User.posts(first:): Int! -> Int"), Severity::Safe);
+        assert_eq!(severity(&diff.changed, "Filter.query: String! -> String"), Severity::Safe);
+        assert_eq!(severity(&diff.changed, "Filter.tag: String -> String!"), Severity::Breaking);
+        // New inputs break operations only when required without a default.
+        assert_eq!(severity(&diff.added, "User.posts(after:)"), Severity::Breaking);
+        assert_eq!(severity(&diff.added, "User.posts(limit:)"), Severity::Safe);
+        assert_eq!(severity(&diff.added, "Filter.page"), Severity::Breaking);
+        assert_eq!(severity(&diff.added, "Filter.size"), Severity::Safe);
+        assert_eq!(severity(&diff.added, "User.email"), Severity::Safe);
+        // Removing deprecated members only warns.
+        assert_eq!(severity(&diff.removed, "User.nick"), Severity::Warning);
+        assert_eq!(severity(&diff.removed, "Color.GREEN"), Severity::Warning);
+        assert_eq!(severity(&diff.removed, "User.age"), Severity::Breaking);
+        assert_eq!(severity(&diff.removed, "Legacy"), Severity::Breaking);
+
+        let records: serde_json::Value = serde_json::from_str(&diff.to_json()).unwrap();
+        assert_eq!(records.as_array().unwrap().len(), 16);
+        assert_eq!(
+            records[0],
+            json!({ "type": "User", "kind": "added", "member": "field", "breaking": false, "severity": "safe", "detail": "User.email" })
+        );
+        assert_eq!(
+            records[1],
+            json!({ "type": "User", "kind": "added", "member": "argument", "breaking": true, "severity": "breaking", "detail": "User.posts(after:)" })
+        );
+        assert_eq!(
+            records[8],
+            json!({ "type": "User", "kind": "removed", "member": "field", "breaking": false, "severity": "warning", "detail": "User.nick" })
+        );
+        assert_eq!(SchemaDiff::default().to_json(), "[]");
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{GQLIntrospector, IntrospectionResult};
+
+/// An output format for introspection results.
+///
+/// Implement this to plug a custom format, such as generated code or documentation,
+/// into `GQLIntrospector::render`.
+///
+/// ```
+/// use gql_introspector::{IntrospectionResult, SchemaFormatter};
+///
+/// struct TypeNames;
+///
+/// impl SchemaFormatter for TypeNames {
+///     fn format(&self, introspection_result: &IntrospectionResult) -> String {
+///         introspection_result
+///             .schema
+///             .types
+///             .iter()
+///             .filter_map(|t| t.name.clone())
+///             .collect::<Vec<_>>()
+///             .join("\n")
+///     }
+/// }
+/// ```
+pub trait SchemaFormatter {
+    /// Formats the introspection result.
+    fn format(&self, introspection_result: &IntrospectionResult) -> String;
+}
+
+impl<F: SchemaFormatter + ?Sized> SchemaFormatter for &F {
+    fn format(&self, introspection_result: &IntrospectionResult) -> String {
+        (**self).format(introspection_result)
+    }
+}
+
+/// Formats the schema as SDL with the default options.
+///
+/// Use `GQLIntrospector::to_sdl` to honor the options set on an introspector.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SdlFormatter;
+
+impl SchemaFormatter for SdlFormatter {
+    fn format(&self, introspection_result: &IntrospectionResult) -> String {
+        GQLIntrospector::new().sdl_of(introspection_result)
+    }
+}
+
+/// Formats the introspection result as pretty-printed JSON, as `to_introspection_json` does.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonFormatter;
+
+impl SchemaFormatter for JsonFormatter {
+    fn format(&self, introspection_result: &IntrospectionResult) -> String {
+        serde_json::to_string_pretty(introspection_result).expect("Introspection results always serialize")
+    }
+}
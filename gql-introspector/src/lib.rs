@@ -1,10 +1,265 @@
+mod diff;
 mod error;
-use std::{collections::HashMap, error::Error, fs::File, io};
+mod format;
+mod markdown;
+mod merge;
+mod model;
+pub mod prelude;
+mod query;
+mod rust;
+mod typescript;
+use std::{collections::{HashMap, HashSet}, env, error::Error, fmt, fs::{self, File}, io, path::Path, sync::{atomic::AtomicBool, Arc, Mutex}, time::{Duration, SystemTime, UNIX_EPOCH}};
 
 use error::GQLInspectorError;
-use gqlclient::{GQLClient, QueryBuilder};
+use gqlclient::{GQLClient, QueryBuilder, Signer};
 use serde::{Deserialize, Serialize};
 
+pub use diff::{Change, Member, SchemaDiff, Severity};
+pub use format::{JsonFormatter, SchemaFormatter, SdlFormatter};
+pub use markdown::MarkdownFormatter;
+pub use model::{EnumType, EnumValue, FieldDef, InputObjectType, InputValue, InterfaceType, ObjectType, ScalarType, SchemaModel, TypeRef, UnionType};
+pub use query::IntrospectionQueryOptions;
+pub use rust::RustFormatter;
+
+
+/// A predicate deciding whether a type is rendered.
+pub type TypeFilter = Arc<dyn Fn(&Type) -> bool + Send + Sync>;
+
+/// A predicate deciding whether a field or input field of the named type is rendered.
+pub type FieldFilter = Arc<dyn Fn(&str, &Field) -> bool + Send + Sync>;
+
+/// The `data` of an introspection query response.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IntrospectionResult {
+    #[serde(rename = "__schema")]
+    pub schema: Schema,
+}
+
+/// The `data` of an introspection response as sent by the server, where `__schema`
+/// may be null when introspection is restricted.
+#[derive(Deserialize)]
+struct IntrospectionData {
+    #[serde(rename = "__schema")]
+    schema: Option<Schema>,
+}
+
+impl IntrospectionData {
+    fn into_result(self) -> Result<IntrospectionResult, GQLInspectorError> {
+        match self.schema {
+            Some(schema) => Ok(IntrospectionResult { schema }),
+            None => Err(GQLInspectorError::new("server returned no schema; introspection may be restricted")),
+        }
+    }
+}
+
+/// The introspected `__schema`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Schema {
+    /// The description of the schema itself, fetched with `IntrospectionQueryOptions::include_schema_description`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "queryType", default, skip_serializing_if = "Option::is_none")]
+    pub query_type: Option<Type>,
+    #[serde(rename = "mutationType", default, skip_serializing_if = "Option::is_none")]
+    pub mutation_type: Option<Type>,
+    #[serde(rename = "subscriptionType", default, skip_serializing_if = "Option::is_none")]
+    pub subscription_type: Option<Type>,
+    pub types: Vec<Type>,
+}
+
+/// An introspected type, or a reference to one through `ofType`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Type {
+    pub kind: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub fields: Option<Vec<Field>>,
+    #[serde(rename = "inputFields")]
+    pub input_fields: Option<Vec<Field>>,
+    pub interfaces: Option<Vec<Type>>,
+    #[serde(rename = "enumValues")]
+    pub enum_values: Option<Vec<Value>>,
+    #[serde(rename = "possibleTypes")]
+    pub possible_types: Option<Vec<Type>>,
+    #[serde(rename = "ofType")]
+    pub of_type: Option<Box<Type>>,
+    /// Directives applied to the type, exposed by servers with the `appliedDirectives` extension.
+    #[serde(rename = "appliedDirectives", default, skip_serializing_if = "Option::is_none")]
+    pub applied_directives: Option<Vec<AppliedDirective>>,
+}
+
+impl Type {
+    fn is_kind(&self, kind: &str) -> bool {
+        self.kind.as_deref() == Some(kind)
+    }
+
+    /// Returns `true` if the directive, given without `@`, is applied to the type.
+    pub fn has_directive(&self, name: &str) -> bool {
+        has_directive(&self.applied_directives, name)
+    }
+
+    /// Returns `true` if this is an `OBJECT` type.
+    pub fn is_object(&self) -> bool {
+        self.is_kind("OBJECT")
+    }
+
+    /// Returns `true` if this is an `INPUT_OBJECT` type.
+    pub fn is_input_object(&self) -> bool {
+        self.is_kind("INPUT_OBJECT")
+    }
+
+    /// Returns `true` if this is an `ENUM` type.
+    pub fn is_enum(&self) -> bool {
+        self.is_kind("ENUM")
+    }
+
+    /// Returns `true` if this is a `SCALAR` type.
+    pub fn is_scalar(&self) -> bool {
+        self.is_kind("SCALAR")
+    }
+
+    /// Returns `true` if this is an `INTERFACE` type.
+    pub fn is_interface(&self) -> bool {
+        self.is_kind("INTERFACE")
+    }
+
+    /// Returns `true` if this is a `UNION` type.
+    pub fn is_union(&self) -> bool {
+        self.is_kind("UNION")
+    }
+
+    /// Returns `true` if the type can be used as an input (scalars, enums and input objects).
+    pub fn is_input_type(&self) -> bool {
+        self.is_scalar() || self.is_enum() || self.is_input_object()
+    }
+
+    /// Returns `true` if the type can be used as an output (everything but input objects).
+    pub fn is_output_type(&self) -> bool {
+        self.is_scalar() || self.is_enum() || self.is_object() || self.is_interface() || self.is_union()
+    }
+}
+
+/// An introspected field, argument or input field.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Field {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub field_type: Option<Type>,
+    #[serde(rename = "defaultValue")]
+    pub default_value: Option<String>,
+    #[serde(rename = "isDeprecated")]
+    pub is_deprecated: Option<bool>,
+    #[serde(rename = "deprecationReason")]
+    pub deprecation_reason: Option<String>,
+    pub args: Option<Vec<Field>>,
+    /// Directives applied to the field, exposed by servers with the `appliedDirectives` extension.
+    #[serde(rename = "appliedDirectives", default, skip_serializing_if = "Option::is_none")]
+    pub applied_directives: Option<Vec<AppliedDirective>>,
+}
+
+impl Field {
+    /// Returns `true` if the directive, given without `@`, is applied to the field.
+    pub fn has_directive(&self, name: &str) -> bool {
+        has_directive(&self.applied_directives, name)
+    }
+}
+
+fn has_directive(directives: &Option<Vec<AppliedDirective>>, name: &str) -> bool {
+    directives.iter().flatten().any(|directive| directive.name.as_deref() == Some(name))
+}
+
+/// An introspected enum value.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Value {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "isDeprecated")]
+    pub is_deprecated: Option<bool>,
+    #[serde(rename = "deprecationReason")]
+    pub deprecation_reason: Option<String>,
+}
+
+/// A directive applied to a type or field, such as `@key(fields: "id")` on a federated subgraph.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppliedDirective {
+    pub name: Option<String>,
+    pub args: Option<Vec<DirectiveArgument>>,
+}
+
+/// An argument of an applied directive, with its value as a GraphQL literal.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectiveArgument {
+    pub name: Option<String>,
+    pub value: Option<String>,
+}
+
+/// How much the introspector reports on stderr.
+///
+/// Warnings are collected and available from `GQLIntrospector::warnings` at every level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Prints nothing.
+    Quiet,
+    /// Prints warnings, such as skipped duplicate fields.
+    #[default]
+    Normal,
+    /// Prints warnings and progress, such as each introspection request.
+    Verbose,
+}
+
+/// A root operation type of a schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl RootKind {
+    const ALL: [RootKind; 3] = [RootKind::Query, RootKind::Mutation, RootKind::Subscription];
+
+    /// Returns the introspected name of this root type, falling back to the conventional one.
+    fn type_name(self, schema: &Schema) -> String {
+        let (root, default) = match self {
+            RootKind::Query => (&schema.query_type, "Query"),
+            RootKind::Mutation => (&schema.mutation_type, "Mutation"),
+            RootKind::Subscription => (&schema.subscription_type, "Subscription"),
+        };
+        root.as_ref().and_then(|t| t.name.clone()).unwrap_or_else(|| default.to_string())
+    }
+}
+
+/// A summary of what an API supports, derived from its root types.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    pub has_mutations: bool,
+    pub has_subscriptions: bool,
+    pub query_field_count: usize,
+    pub mutation_field_count: usize,
+    pub subscription_field_count: usize,
+    pub type_count: usize,
+}
+
+/// An argument of a field, as returned by `GQLIntrospector::arguments_of`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgInfo {
+    pub name: String,
+    /// The SDL type of the argument, e.g. `[ID!]!`.
+    pub type_name: String,
+    /// The default value as a GraphQL literal, e.g. `10` or `"en"`.
+    pub default_value: Option<String>,
+    pub description: Option<String>,
+}
+
+/// A deprecated field or enum value, as returned by `GQLIntrospector::deprecated_only`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+    /// The type declaring the field or enum value.
+    pub type_name: String,
+    pub name: String,
+    pub reason: Option<String>,
+}
 
 /// `GQLIntrospector` is a utility for introspecting GraphQL schemas.
 /// 
@@ -30,252 +285,1248 @@ use serde::{Deserialize, Serialize};
 /// 
 /// println!("Schema introspection and write completed.");
 /// ```
+pub struct GQLIntrospector {
+    headers: HashMap<String, String>,
+    introspection_result: Option<IntrospectionResult>,
+    schema: String,
+    include_introspection_types: bool,
+    use_get: bool,
+    allow_insecure_http: bool,
+    strip_descriptions: bool,
+    endpoint: Option<String>,
+    header_comment: bool,
+    sort_enum_values: bool,
+    extended_types: HashSet<String>,
+    root_names: Option<[Option<String>; 3]>,
+    query_options: IntrospectionQueryOptions,
+    fetched_from: Option<(String, SystemTime)>,
+    signer: Option<(String, Signer)>,
+    timeout: Option<Duration>,
+    cancellation: Option<Arc<AtomicBool>>,
+    type_filter: Option<TypeFilter>,
+    field_filter: Option<FieldFilter>,
+    excluded_directives: Vec<String>,
+    skip_emptied_types: bool,
+    reachable_roots: Option<Vec<RootKind>>,
+    kind_order: Option<Vec<String>>,
+    create_dirs: bool,
+    verbosity: Verbosity,
+    warnings: Mutex<Vec<String>>,
+}
+
+impl fmt::Debug for GQLIntrospector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GQLIntrospector")
+            .field("headers", &self.headers)
+            .field("introspection_result", &self.introspection_result)
+            .field("schema", &self.schema)
+            .field("include_introspection_types", &self.include_introspection_types)
+            .field("use_get", &self.use_get)
+            .field("allow_insecure_http", &self.allow_insecure_http)
+            .field("strip_descriptions", &self.strip_descriptions)
+            .field("endpoint", &self.endpoint)
+            .field("header_comment", &self.header_comment)
+            .field("sort_enum_values", &self.sort_enum_values)
+            .field("extended_types", &self.extended_types)
+            .field("root_names", &self.root_names)
+            .field("query_options", &self.query_options)
+            .field("fetched_from", &self.fetched_from)
+            .field("signer", &self.signer.as_ref().map(|(header, _)| header))
+            .field("timeout", &self.timeout)
+            .field("cancellation", &self.cancellation)
+            .field("type_filter", &self.type_filter.is_some())
+            .field("field_filter", &self.field_filter.is_some())
+            .field("excluded_directives", &self.excluded_directives)
+            .field("skip_emptied_types", &self.skip_emptied_types)
+            .field("reachable_roots", &self.reachable_roots)
+            .field("kind_order", &self.kind_order)
+            .field("create_dirs", &self.create_dirs)
+            .field("verbosity", &self.verbosity)
+            .field("warnings", &self.warnings())
+            .finish()
+    }
+}
+
+impl Default for GQLIntrospector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GQLIntrospector {
+    /// Creates a new instance of `GQLIntrospector`.
+    ///
+    /// # Returns
+    /// 
+    /// A new `GQLIntrospector` instance.
+    pub fn new() -> Self {
+        Self {
+            headers: HashMap::new(),
+            introspection_result: None,
+            schema: String::new(),
+            include_introspection_types: false,
+            use_get: false,
+            allow_insecure_http: false,
+            strip_descriptions: false,
+            endpoint: None,
+            header_comment: false,
+            sort_enum_values: false,
+            extended_types: HashSet::new(),
+            root_names: None,
+            query_options: IntrospectionQueryOptions::default(),
+            fetched_from: None,
+            signer: None,
+            timeout: None,
+            cancellation: None,
+            type_filter: None,
+            field_filter: None,
+            excluded_directives: Vec::new(),
+            skip_emptied_types: false,
+            reachable_roots: None,
+            kind_order: None,
+            create_dirs: false,
+            verbosity: Verbosity::default(),
+            warnings: Mutex::new(Vec::new()),
+        }
+    }
+    /// Creates a new `GQLIntrospector` configured from environment variables.
+    ///
+    /// * `GQL_ENDPOINT` - The URL of the GraphQL endpoint (required).
+    /// * `GQL_AUTH_TOKEN` - A bearer token sent in the `Authorization` header (optional).
+    /// * `GQL_USER_AGENT` - The `User-Agent` header (optional).
+    ///
+    /// # Returns
+    ///
+    /// A result containing the configured `GQLIntrospector` instance or an error if `GQL_ENDPOINT` is not set.
+    pub fn from_env() -> Result<Self, Box<dyn Error>> {
+        let endpoint = env::var("GQL_ENDPOINT")
+            .map_err(|_| GQLInspectorError::new("GQL_ENDPOINT environment variable is not set"))?;
+
+        let mut introspector = Self::new().with_endpoint(&endpoint);
+        if let Ok(token) = env::var("GQL_AUTH_TOKEN") {
+            introspector = introspector.add("Authorization", &format!("Bearer {}", token));
+        }
+        if let Ok(user_agent) = env::var("GQL_USER_AGENT") {
+            introspector = introspector.add("User-Agent", &user_agent);
+        }
+        Ok(introspector)
+    }
+
+    /// Sets the endpoint used by `introspect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the GraphQL endpoint.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_endpoint(mut self, url: &str) -> Self {
+        self.endpoint = Some(url.to_string());
+        self
+    }
+
+    /// Returns the configured endpoint, if any.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// Adds a header to be used in the GraphQL request.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the header.
+    /// * `value` - The value of the header.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn add(mut self, key: &str, value: &str) -> Self {
+        self.headers.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Identifies the introspector to an Apollo gateway.
+    ///
+    /// Sets the `apollographql-client-name` and `apollographql-client-version` headers,
+    /// which Apollo-managed graphs commonly require on every request, introspection included.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The client name.
+    /// * `version` - The client version.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_client_info(self, name: &str, version: &str) -> Self {
+        self.add("apollographql-client-name", name)
+            .add("apollographql-client-version", version)
+    }
+
+    /// Sets how much is reported on stderr.
+    ///
+    /// Use `Verbosity::Quiet` when embedding the introspector in another tool, and
+    /// read `warnings` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `verbosity` - The verbosity level.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Returns the warnings from the most recent rendering, e.g. by `build`, `to_sdl` or `write_split`.
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.lock().map(|warnings| warnings.clone()).unwrap_or_default()
+    }
+
+    fn warn(&self, message: String) {
+        if self.verbosity != Verbosity::Quiet {
+            eprintln!("{}", message);
+        }
+        if let Ok(mut warnings) = self.warnings.lock() {
+            warnings.push(message);
+        }
+    }
+
+    fn clear_warnings(&self) {
+        if let Ok(mut warnings) = self.warnings.lock() {
+            warnings.clear();
+        }
+    }
+
+    fn info(&self, message: String) {
+        if self.verbosity == Verbosity::Verbose {
+            eprintln!("{}", message);
+        }
+    }
+
+    /// Sets whether the built-in introspection types (`__Schema`, `__Type`, ...) are rendered.
+    ///
+    /// They are skipped by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `include` - Whether to include the introspection types in the output.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn include_introspection_types(mut self, include: bool) -> Self {
+        self.include_introspection_types = include;
+        self
+    }
+
+    /// Omits all descriptions from the generated SDL, for a minimal schema.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn no_descriptions(mut self) -> Self {
+        self.strip_descriptions = true;
+        self
+    }
+
+    /// Sets whether the generated SDL starts with a comment recording where and when
+    /// the schema was fetched, e.g.
+    /// `# Generated by gql-rs from https://api.example.com/graphql on 2024-01-01T00:00:00Z — do not edit`.
+    ///
+    /// Off by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to write the header comment.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_header_comment(mut self, enabled: bool) -> Self {
+        self.header_comment = enabled;
+        self
+    }
+
+    /// Sets whether enum values are written in alphabetical order instead of the
+    /// server's, for stable output from gateways that return them in varying order.
+    ///
+    /// Off by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to sort enum values.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn sort_enum_values(mut self, enabled: bool) -> Self {
+        self.sort_enum_values = enabled;
+        self
+    }
+
+    /// Renders the named types with the `extend` keyword, e.g. `extend type User { ... }`,
+    /// for SDL meant to be layered onto a base schema. Other types render normally.
+    ///
+    /// Type extensions cannot carry descriptions, so none are written for these types.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_names` - The names of the types to render as extensions.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn as_extension(mut self, type_names: &[&str]) -> Self {
+        self.extended_types = type_names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Overrides the root operation type names written in the `schema { ... }` block.
+    ///
+    /// The block is otherwise only written when the introspected root types are not
+    /// named `Query`, `Mutation` and `Subscription`. Only the block is affected; the
+    /// type definitions keep their introspected names.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The name of the query root type.
+    /// * `mutation` - The name of the mutation root type, if any.
+    /// * `subscription` - The name of the subscription root type, if any.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_root_names(mut self, query: &str, mutation: Option<&str>, subscription: Option<&str>) -> Self {
+        self.root_names = Some([Some(query.to_string()), mutation.map(str::to_string), subscription.map(str::to_string)]);
+        self
+    }
+
+    /// Sets which parts of the schema the introspection query fetches.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options used to generate the introspection query.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_query_options(mut self, options: IntrospectionQueryOptions) -> Self {
+        self.query_options = options;
+        self
+    }
+
+    /// Sets whether introspection may be sent to a plain `http://` endpoint.
+    ///
+    /// Off by default so that headers such as tokens are not sent in cleartext;
+    /// loopback hosts are always allowed. See `GQLClient::allow_insecure_http`.
+    ///
+    /// # Arguments
+    ///
+    /// * `allow` - Whether to allow `http://` endpoints.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn allow_insecure_http(mut self, allow: bool) -> Self {
+        self.allow_insecure_http = allow;
+        self
+    }
+
+    /// Sets a callback that signs the introspection request body, for endpoints that
+    /// reject unsigned requests.
+    ///
+    /// The callback receives the exact body bytes that will be sent, or with `use_get`
+    /// the percent-encoded URL query string, and its result is set as the value of
+    /// `header_name`, as with `GQLClient::with_signer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `header_name` - The name of the header carrying the signature.
+    /// * `signer` - A function computing the signature from the signed bytes.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_signer<F>(mut self, header_name: &str, signer: F) -> Self
+    where
+        F: Fn(&[u8]) -> String + Send + Sync + 'static,
+    {
+        self.signer = Some((header_name.to_string(), Arc::new(signer)));
+        self
+    }
+
+    /// Renders only the types for which `filter` returns `true`, e.g. to publish a
+    /// public subset of an internal schema.
+    ///
+    /// Applies to `build`, `to_sdl` and `write_split`. Fields that return an excluded
+    /// type should be excluded with `with_field_filter` as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - A predicate over the introspected type.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_type_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Type) -> bool + Send + Sync + 'static,
+    {
+        self.type_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Renders only the fields and input fields for which `filter` returns `true`, e.g.
+    /// to leave out every field whose name starts with `_`.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - A predicate over the name of the owning type and the field.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_field_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&str, &Field) -> bool + Send + Sync + 'static,
+    {
+        self.field_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Sets whether types left without fields by the field filter are skipped instead of
+    /// being written with an empty body.
+    ///
+    /// Off by default. Types that had no fields to begin with are always written. As with
+    /// `with_type_filter`, fields returning a skipped type should be filtered out as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether emptied types are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn skip_emptied_types(mut self, enabled: bool) -> Self {
+        self.skip_emptied_types = enabled;
+        self
+    }
+
+    /// Leaves out every type and field carrying the given applied directive, such as
+    /// `internal` for `@internal`, producing a client-facing schema.
+    ///
+    /// Applied directives are only available from servers supporting the
+    /// `appliedDirectives` extension; see the `federation` feature. Applies on top of
+    /// `with_type_filter` and `with_field_filter`, and may be called for several directives.
+    ///
+    /// # Arguments
+    ///
+    /// * `directive` - The name of the directive, without `@`.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn exclude_directive(mut self, directive: &str) -> Self {
+        self.excluded_directives.push(directive.to_string());
+        self
+    }
+
+    /// Renders only the given root operation types and the types reachable from them,
+    /// e.g. `&[RootKind::Mutation, RootKind::Subscription]` for a "writes only" schema.
+    ///
+    /// A type is reachable through field, argument and input field types, implemented
+    /// interfaces, union members and interface implementations. Other root types are
+    /// left out of the `schema` block.
+    ///
+    /// # Arguments
+    ///
+    /// * `roots` - The root operation types to start from.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn reachable_from(mut self, roots: &[RootKind]) -> Self {
+        self.reachable_roots = Some(roots.to_vec());
+        self
+    }
+
+    /// Groups the types of the SDL by kind: scalars, enums, interfaces, objects, input
+    /// objects and then unions. Within a group, types keep the server's order.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn group_by_kind(self) -> Self {
+        self.with_kind_order(&["SCALAR", "ENUM", "INTERFACE", "OBJECT", "INPUT_OBJECT", "UNION"])
+    }
+
+    /// Groups the types of the SDL by kind in the given order, such as
+    /// `&["OBJECT", "INPUT_OBJECT", "ENUM"]`. Kinds that are not listed come last.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The introspection kinds, e.g. `SCALAR` or `INPUT_OBJECT`, in the order to render them.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_kind_order(mut self, order: &[&str]) -> Self {
+        self.kind_order = Some(order.iter().map(|kind| kind.to_string()).collect());
+        self
+    }
+
+    /// Sets how long the introspection request may take, independently of the timeouts
+    /// used for regular queries, since responses for large schemas can be slow.
+    ///
+    /// Defaults to the client's 30 seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum duration of the introspection request.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Aborts introspection once `token` is set, e.g. when the user hits Ctrl-C, failing
+    /// with `gqlclient::RequestError::Cancelled`. See `GQLClient::with_cancellation`.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The flag to set to cancel introspection.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn with_cancellation(mut self, token: Arc<AtomicBool>) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Sets whether introspection is sent over GET instead of POST.
+    ///
+    /// Useful for read-only endpoints that reject POST requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to use the GET transport.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn use_get(mut self, enabled: bool) -> Self {
+        self.use_get = enabled;
+        self
+    }
+
+    /// Retrieves the schema from the provided URL.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the GraphQL endpoint.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the updated `GQLIntrospector` instance or an error.
+    pub fn get_schema(mut self, url: &str) -> Result<Self, Box<dyn Error>> {
+        self.fetch(url)?;
+        Ok(self)
+    }
+
+    /// Retrieves the schema from the configured endpoint.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the updated `GQLIntrospector` instance or an error if no endpoint is configured.
+    pub fn introspect(self) -> Result<Self, Box<dyn Error>> {
+        let endpoint = self.endpoint.clone().ok_or_else(|| GQLInspectorError::new("No endpoint configured"))?;
+        self.get_schema(&endpoint)
+    }
+
+    /// Retrieves the schema from the provided URL without consuming the introspector.
+    ///
+    /// The introspection result is kept on the instance, so it can be rendered
+    /// into several outputs without another round-trip.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the GraphQL endpoint.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating success or failure.
+    pub fn fetch(&mut self, url: &str) -> Result<(), Box<dyn Error>> {
+        self.info(format!("Introspecting {}", url));
+        let introspection_result = self.run_introspection(url, &self.query_options.to_query())?;
+        self.info(format!("Received {} types from {}", introspection_result.schema.types.len(), url));
+        self.introspection_result = Some(introspection_result);
+        self.fetched_from = Some((url.to_string(), SystemTime::now()));
+        Ok(())
+    }
+
+    /// Builds a client and query with the headers, signer and timeout of this introspector.
+    fn request(&self, url: &str, query: &str) -> (GQLClient, QueryBuilder) {
+        let mut client = GQLClient::new(url).use_get(self.use_get).allow_insecure_http(self.allow_insecure_http);
+        if let Some((header_name, signer)) = &self.signer {
+            let signer = Arc::clone(signer);
+            client = client.with_signer(header_name, move |bytes| signer(bytes));
+        }
+        if let Some(timeout) = self.timeout {
+            client = client.with_timeout(timeout);
+        }
+        if let Some(token) = &self.cancellation {
+            client = client.with_cancellation(Arc::clone(token));
+        }
+        let mut query_builder = QueryBuilder::new(query);
+
+        for (key, value) in &self.headers {
+            query_builder.set_header(key, value);
+        }
+        (client, query_builder)
+    }
+
+    /// Sends an introspection query with the headers, signer and timeout of this introspector.
+    fn run_introspection(&self, url: &str, query: &str) -> Result<IntrospectionResult, Box<dyn Error>> {
+        let (client, query_builder) = self.request(url, query);
+        Ok(client.run_query::<IntrospectionData>(&query_builder)?.into_result()?)
+    }
+
+    /// Checks that `url` is reachable and answers GraphQL, by sending `{ __typename }`.
+    ///
+    /// This is a quick preflight before a full introspection, using the same headers,
+    /// signer and timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the GraphQL endpoint.
+    ///
+    /// # Returns
+    ///
+    /// A result indicating success, or an error saying whether the endpoint is unreachable,
+    /// rejected the credentials or did not answer with a GraphQL response.
+    pub fn ping(&self, url: &str) -> Result<(), Box<dyn Error>> {
+        let (client, query_builder) = self.request(url, "{ __typename }");
+        let err = match client.strict().run_query::<serde_json::Value>(&query_builder) {
+            Ok(data) if data["__typename"].is_string() => return Ok(()),
+            Ok(_) => return Err(Box::new(GQLInspectorError::new(&format!("{} did not return a GraphQL response (no __typename in data)", url)))),
+            Err(err) => err,
+        };
+
+        let message = if err.is::<gqlclient::RequestError>() {
+            format!("{} is unreachable: {}", url, err)
+        } else if let Some(strict) = err.downcast_ref::<gqlclient::StrictModeError>() {
+            let unauthorized = strict.status.is_some_and(|status| status.as_u16() == 401 || status.as_u16() == 403)
+                || strict.errors.iter().any(|error| matches!(error.code(), Some("UNAUTHENTICATED" | "FORBIDDEN")));
+            if unauthorized {
+                format!("{} rejected the request as unauthorized ({}); check the configured headers", url, err)
+            } else if strict.errors.is_empty() {
+                format!("{} did not return a GraphQL response ({})", url, err)
+            } else {
+                format!("{} answered GraphQL but returned errors ({})", url, err)
+            }
+        } else if err.is::<gqlclient::DeserializeError>() {
+            format!("{} did not return a GraphQL response ({})", url, err)
+        } else {
+            return Err(err);
+        };
+        Err(Box::new(GQLInspectorError::new(&message)))
+    }
+
+    /// Lists the deprecated fields and enum values of the schema at `url`.
+    ///
+    /// This sends a trimmed introspection query fetching only the names and deprecation
+    /// status of fields and enum values, which is much lighter than a full introspection.
+    /// The stored introspection result is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the GraphQL endpoint.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the deprecated items in schema order, or an error.
+    pub fn deprecated_only(&self, url: &str) -> Result<Vec<Deprecation>, Box<dyn Error>> {
+        let members = "(includeDeprecated: true) { name isDeprecated deprecationReason }";
+        let query = format!("query {{ __schema {{ types {{ name fields{0} enumValues{0} }} }} }}", members);
+        self.info(format!("Fetching deprecations from {}", url));
+        let introspection_result = self.run_introspection(url, &query)?;
+
+        let mut deprecations = Vec::new();
+        for t in &introspection_result.schema.types {
+            let Some(type_name) = &t.name else { continue };
+            let fields = t.fields.iter().flatten().map(|field| (&field.name, field.is_deprecated, &field.deprecation_reason));
+            let values = t.enum_values.iter().flatten().map(|value| (&value.name, value.is_deprecated, &value.deprecation_reason));
+            for (name, is_deprecated, reason) in fields.chain(values) {
+                if let (Some(name), Some(true)) = (name, is_deprecated) {
+                    deprecations.push(Deprecation { type_name: type_name.clone(), name: name.clone(), reason: reason.clone() });
+                }
+            }
+        }
+        Ok(deprecations)
+    }
+
+    /// Introspects the endpoint and reports what changed since the snapshot at `snapshot_path`,
+    /// then replaces the snapshot with the current schema.
+    ///
+    /// The snapshot is the introspection JSON written by `to_introspection_json`. When it
+    /// does not exist yet it is created and the diff is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The URL of the GraphQL endpoint.
+    /// * `snapshot_path` - The path of the stored snapshot.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the changes since the previous snapshot or an error.
+    pub fn check_changes<P: AsRef<Path>>(&mut self, endpoint: &str, snapshot_path: P) -> Result<SchemaDiff, Box<dyn Error>> {
+        self.fetch(endpoint)?;
+        self.update_snapshot(snapshot_path.as_ref())
+    }
+
+    fn update_snapshot(&self, snapshot_path: &Path) -> Result<SchemaDiff, Box<dyn Error>> {
+        let current = self.to_introspection_json()?;
+        let diff = match fs::read_to_string(snapshot_path) {
+            Ok(previous) => {
+                let previous: IntrospectionResult = serde_json::from_str(&previous)?;
+                let current = self.introspection_result.as_ref().ok_or("Introspection result is missing")?;
+                SchemaDiff::between(&previous, current)
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => SchemaDiff::default(),
+            Err(err) => return Err(err.into()),
+        };
+        fs::write(snapshot_path, current)?;
+        Ok(diff)
+    }
+
+    /// Returns the introspection result, if the schema has been retrieved.
+    pub fn introspection_result(&self) -> Option<&IntrospectionResult> {
+        self.introspection_result.as_ref()
+    }
+
+    /// Merges another introspection result, e.g. from a different subgraph, into this one.
+    ///
+    /// This is a best-effort stitch rather than a federation-aware composition: types are
+    /// deduplicated by name and their fields, enum values and union members are combined.
+    /// Conflicting definitions keep the existing one and are reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The introspection result to merge in.
+    ///
+    /// # Returns
+    ///
+    /// A description of every conflict encountered, empty if the schemas merged cleanly.
+    pub fn merge(&mut self, other: IntrospectionResult) -> Vec<String> {
+        match &mut self.introspection_result {
+            Some(introspection_result) => merge::merge_into(introspection_result, other),
+            None => {
+                self.introspection_result = Some(other);
+                Vec::new()
+            }
+        }
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct IntrospectionResult {
-    #[serde(rename = "__schema")]
-    schema: Schema,
-}
+    /// Returns the introspected type with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the type.
+    pub fn get_type(&self, name: &str) -> Option<&Type> {
+        self.introspection_result
+            .as_ref()?
+            .schema
+            .types
+            .iter()
+            .find(|t| t.name.as_deref() == Some(name))
+    }
 
-const INTROSPECTION_QUERY: &str = r#"
-query {
-    __schema {
-        types {
-            kind
-            name
-            description
-            fields(includeDeprecated: true) {
-                name
-                description
-                args {
-                    name
-                    description
-                    type {
-                        kind
-                        name
-                        ofType {
-                            kind
-                            name
-                            ofType {
-                                kind
-                                name
-                                ofType {
-                                    kind
-                                    name
-                                }
-                            }
-                        }
-                    }
-                    defaultValue
-                }
-                type {
-                    kind
-                    name
-                    ofType {
-                        kind
-                        name
-                        ofType {
-                            kind
-                            name
-                            ofType {
-                                kind
-                                name
-                            }
-                        }
-                    }
+    /// Summarizes the operations the schema supports, for orientation on an unfamiliar API.
+    ///
+    /// Root types are taken from the introspected `queryType`, `mutationType` and
+    /// `subscriptionType`, falling back to types named `Query`, `Mutation` and
+    /// `Subscription`. Built-in introspection types are not counted.
+    pub fn capabilities(&self) -> Capabilities {
+        let Some(introspection_result) = &self.introspection_result else {
+            return Capabilities::default();
+        };
+        let schema = &introspection_result.schema;
+
+        let field_count = |root: &Option<Type>, default: &str| {
+            let name = root.as_ref().and_then(|t| t.name.as_deref()).unwrap_or(default);
+            self.get_type(name).map(|t| t.fields.as_ref().map_or(0, Vec::len))
+        };
+        let mutation_field_count = field_count(&schema.mutation_type, "Mutation");
+        let subscription_field_count = field_count(&schema.subscription_type, "Subscription");
+
+        Capabilities {
+            has_mutations: mutation_field_count.is_some(),
+            has_subscriptions: subscription_field_count.is_some(),
+            query_field_count: field_count(&schema.query_type, "Query").unwrap_or(0),
+            mutation_field_count: mutation_field_count.unwrap_or(0),
+            subscription_field_count: subscription_field_count.unwrap_or(0),
+            type_count: schema
+                .types
+                .iter()
+                .filter(|t| !t.name.as_deref().unwrap_or_default().starts_with("__"))
+                .count(),
+        }
+    }
+
+    /// Builds a typed model of the schema, with structured type references, from the
+    /// introspection result.
+    ///
+    /// Built-in introspection types are left out unless `include_introspection_types` is set.
+    /// The model is empty if the schema has not been retrieved.
+    pub fn model(&self) -> SchemaModel {
+        match &self.introspection_result {
+            Some(introspection_result) => SchemaModel::build(introspection_result, self.rendered_types(introspection_result)),
+            None => SchemaModel::default(),
+        }
+    }
+
+    /// Lists the fields of a type with their SDL type strings, e.g. `("friends", "[User!]!")`.
+    ///
+    /// Input fields are listed for input object types. Unknown types have no fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - The name of the type.
+    pub fn fields_of(&self, type_name: &str) -> Vec<(String, String)> {
+        let Some(t) = self.get_type(type_name) else {
+            return Vec::new();
+        };
+
+        t.fields
+            .iter()
+            .chain(t.input_fields.iter())
+            .flatten()
+            .filter_map(|field| Some((field.name.clone()?, Self::format_type(field.field_type.as_ref()?))))
+            .collect()
+    }
+
+    /// Lists the interfaces a type implements, in the order the schema declares them.
+    ///
+    /// Unknown types and types that implement no interfaces return an empty list.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - The name of the type.
+    pub fn interfaces_of(&self, type_name: &str) -> Vec<String> {
+        let Some(t) = self.get_type(type_name) else {
+            return Vec::new();
+        };
+
+        t.interfaces.iter().flatten().filter_map(|iface| iface.name.clone()).collect()
+    }
+
+    /// Checks that every type referenced by a field, argument, input field, union member
+    /// or implemented interface is among the introspected types.
+    ///
+    /// Built-in scalars count as defined. The list is empty if the schema is
+    /// self-consistent or has not been retrieved.
+    ///
+    /// # Returns
+    ///
+    /// A description of each dangling reference, e.g. `User.friend references undefined type Friend`.
+    pub fn check_references(&self) -> Vec<String> {
+        let Some(introspection_result) = &self.introspection_result else {
+            return Vec::new();
+        };
+        let model = SchemaModel::build(introspection_result, introspection_result.schema.types.iter());
+        let defined: HashSet<&str> = introspection_result.schema.types.iter().filter_map(|t| t.name.as_deref()).collect();
+        let is_undefined =
+            |name: &str| !defined.contains(name) && !matches!(name, "Int" | "Float" | "String" | "Boolean" | "ID");
+
+        let mut problems = Vec::new();
+        let mut check_fields = |type_name: &str, fields: &[FieldDef]| {
+            for field in fields {
+                let named = field.field_type.named_type();
+                if is_undefined(named) {
+                    problems.push(format!("{}.{} references undefined type {}", type_name, field.name, named));
                 }
-                isDeprecated
-                deprecationReason
-            }
-            inputFields {
-                name
-                description
-                type {
-                    kind
-                    name
-                    ofType {
-                        kind
-                        name
-                        ofType {
-                            kind
-                            name
-                        }
+                for arg in &field.args {
+                    let named = arg.value_type.named_type();
+                    if is_undefined(named) {
+                        problems.push(format!("{}.{}({}) references undefined type {}", type_name, field.name, arg.name, named));
                     }
                 }
-                defaultValue
-            }
-            interfaces {
-                kind
-                name
-                ofType {
-                    kind
-                    name
-                }
             }
-            enumValues(includeDeprecated: true) {
-                name
-                description
-                isDeprecated
-                deprecationReason
-            }
-            possibleTypes {
-                kind
-                name
-                ofType {
-                    kind
-                    name
+        };
+        for t in &model.objects {
+            check_fields(&t.name, &t.fields);
+        }
+        for t in &model.interfaces {
+            check_fields(&t.name, &t.fields);
+        }
+
+        for t in &model.objects {
+            for iface in t.interfaces.iter().filter(|iface| is_undefined(iface)) {
+                problems.push(format!("{} implements undefined interface {}", t.name, iface));
+            }
+        }
+        for t in &model.inputs {
+            for field in &t.fields {
+                let named = field.value_type.named_type();
+                if is_undefined(named) {
+                    problems.push(format!("{}.{} references undefined type {}", t.name, field.name, named));
                 }
             }
         }
+        for t in &model.unions {
+            for member in t.members.iter().filter(|member| is_undefined(member)) {
+                problems.push(format!("Union {} has undefined member {}", t.name, member));
+            }
+        }
+        problems
     }
-}
-"#;
-
-#[derive(Debug, Serialize, Deserialize)]
-struct Schema {
-    types: Vec<Type>,
-}
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Type {
-    kind: Option<String>,
-    name: Option<String>,
-    description: Option<String>,
-    fields: Option<Vec<Field>>,
-    #[serde(rename = "inputFields")]
-    input_fields: Option<Vec<Field>>,
-    interfaces: Option<Vec<Type>>,
-    #[serde(rename = "enumValues")]
-    enum_values: Option<Vec<Value>>,
-    #[serde(rename = "possibleTypes")]
-    possible_types: Option<Vec<Type>>,
-    #[serde(rename = "ofType")]
-    of_type: Option<Box<Type>>,
-}
+    /// Lists the arguments of a field, in the order the schema declares them.
+    ///
+    /// Unknown types and fields return an empty list.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - The name of the object or interface type.
+    /// * `field_name` - The name of the field.
+    pub fn arguments_of(&self, type_name: &str, field_name: &str) -> Vec<ArgInfo> {
+        let Some(field) = self
+            .get_type(type_name)
+            .and_then(|t| t.fields.as_ref()?.iter().find(|field| field.name.as_deref() == Some(field_name)))
+        else {
+            return Vec::new();
+        };
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Field {
-    name: Option<String>,
-    description: Option<String>,
-    #[serde(rename = "type")]
-    field_type: Option<Type>,
-    #[serde(rename = "defaultValue")]
-    default_value: Option<String>,
-    #[serde(rename = "isDeprecated")]
-    is_deprecated: Option<bool>,
-    #[serde(rename = "deprecationReason")]
-    deprecation_reason: Option<String>,
-    args: Option<Vec<Field>>,
-}
+        field
+            .args
+            .iter()
+            .flatten()
+            .filter_map(|arg| {
+                Some(ArgInfo {
+                    name: arg.name.clone()?,
+                    type_name: Self::format_type(arg.field_type.as_ref()?),
+                    default_value: arg.default_value.clone(),
+                    description: arg.description.clone(),
+                })
+            })
+            .collect()
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct Value {
-    name: Option<String>,
-    description: Option<String>,
-    #[serde(rename = "isDeprecated")]
-    is_deprecated: Option<bool>,
-    #[serde(rename = "deprecationReason")]
-    deprecation_reason: Option<String>,
-}
+    /// Generates TypeScript types for the results of the operations in a query document.
+    ///
+    /// Each operation becomes an `export type <Name>Result`, named after the operation or
+    /// its root type when it is anonymous. Aliases become the keys of the result, and fields
+    /// selected through a fragment on a narrower type are optional.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query document, with any fragments it spreads.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the TypeScript declarations, or an error if the document is invalid
+    /// or selects fields the schema does not define.
+    pub fn typescript_for_query(&self, query: &str) -> Result<String, Box<dyn Error>> {
+        match &self.introspection_result {
+            Some(introspection_result) => {
+                typescript::operation_types(introspection_result, query).map_err(|message| GQLInspectorError::new(&message).into())
+            }
+            None => Err(Box::new(GQLInspectorError::new("Introspection result is missing"))),
+        }
+    }
 
-#[derive(Debug)]
-pub struct GQLIntrospector {
-    headers: HashMap<String, String>,
-    introspection_result: Option<IntrospectionResult>,
-    schema: String,
-}
+    /// Builds the schema from the introspection result.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the updated `GQLIntrospector` instance or an error.
+    pub fn build(mut self) -> Result<Self, Box<dyn Error>> {
+        if self.introspection_result.is_some() {
+            self.schema = self.to_sdl()?;
+        }
+        Ok(self)
+    }
 
-impl GQLIntrospector {
-    /// Creates a new instance of `GQLIntrospector`.
+    /// Renders the introspection result as SDL without consuming the introspector.
     ///
     /// # Returns
-    /// 
-    /// A new `GQLIntrospector` instance.
-    pub fn new() -> Self {
-        Self {
-            headers: HashMap::new(),
-            introspection_result: None,
-            schema: String::new(),
+    ///
+    /// A result containing the SDL string or an error if no introspection has been performed.
+    pub fn to_sdl(&self) -> Result<String, Box<dyn Error>> {
+        match &self.introspection_result {
+            Some(introspection_result) => Ok(self.sdl_of(introspection_result)),
+            None => Err(Box::new(GQLInspectorError::new("Introspection result is missing"))),
         }
     }
-    /// Adds a header to be used in the GraphQL request.
+
+    /// Renders the introspection result as UTF-8 SDL bytes, e.g. for embedding the schema
+    /// as an asset or adding it to an archive.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the SDL bytes or an error if no introspection has been performed.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(self.to_sdl()?.into_bytes())
+    }
+
+    /// Renders the introspection result as SDL into any writer, such as stdout, a socket
+    /// or an archive entry.
     ///
     /// # Arguments
     ///
-    /// * `key` - The name of the header.
-    /// * `value` - The value of the header.
+    /// * `writer` - The destination of the SDL.
     ///
     /// # Returns
     ///
-    /// The updated `GQLIntrospector` instance.
-    pub fn add(mut self, key: &str, value: &str) -> Self {
-        self.headers.insert(key.to_string(), value.to_string());
-        self
+    /// A result indicating success, or an error if no introspection has been performed or writing failed.
+    pub fn write_to<W: io::Write>(&self, mut writer: W) -> Result<(), Box<dyn Error>> {
+        writer.write_all(&self.to_bytes()?)?;
+        Ok(())
     }
 
-    /// Retrieves the schema from the provided URL.
+    /// Renders the introspection result with a custom `SchemaFormatter`.
     ///
     /// # Arguments
     ///
-    /// * `url` - The URL of the GraphQL endpoint.
+    /// * `formatter` - The formatter producing the output.
     ///
     /// # Returns
     ///
-    /// A result containing the updated `GQLIntrospector` instance or an error.
-    pub fn get_schema(mut self, url: &str) -> Result<Self, Box<dyn Error>> {
-        let client = GQLClient::new(url);
-        let mut query_builder = QueryBuilder::new(INTROSPECTION_QUERY);
+    /// A result containing the formatted output or an error if no introspection has been performed.
+    pub fn render(&self, formatter: impl SchemaFormatter) -> Result<String, Box<dyn Error>> {
+        match &self.introspection_result {
+            Some(introspection_result) => Ok(formatter.format(introspection_result)),
+            None => Err(Box::new(GQLInspectorError::new("Introspection result is missing"))),
+        }
+    }
 
-        for (key, value) in &self.headers {
-            query_builder.set_header(key, value);
+    /// Renders an introspection result as SDL using the options of this introspector.
+    pub(crate) fn sdl_of(&self, introspection_result: &IntrospectionResult) -> String {
+        self.clear_warnings();
+        let mut sb = String::new();
+        if self.header_comment {
+            self.write_header_comment(&mut sb);
         }
+        self.write_schema_block(&mut sb, &introspection_result.schema, false);
+        let implements_iface_map = Self::build_implements_interface_map(introspection_result);
 
-        match client.run_query::<IntrospectionResult>(&query_builder) {
-            Ok(response) => self.introspection_result = Some(response),
-            Err(e) => return Err(e)
+        let mut types: Vec<_> = self.rendered_types(introspection_result).collect();
+        if let Some(order) = &self.kind_order {
+            // The sort is stable, so types of the same kind keep their order.
+            types.sort_by_key(|t| order.iter().position(|kind| t.kind.as_ref() == Some(kind)).unwrap_or(order.len()));
         }
-        Ok(self)
+        for t in types {
+            self.write_type(&mut sb, t, &implements_iface_map);
+        }
+
+        sb
     }
 
-    /// Builds the schema from the introspection result.
+    /// Writes the schema split into one file per type category in `dir`, which is
+    /// created if needed: `schema.graphql` (the root operation types), `objects.graphql`,
+    /// `inputs.graphql`, `enums.graphql`, `scalars.graphql`, `interfaces.graphql` and
+    /// `unions.graphql`.
+    ///
+    /// Every file is written, even when empty, so stale files from a previous run do not
+    /// linger. Files that only define enums or scalars are valid on their own; the others
+    /// reference types from the rest of the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to write the files to.
     ///
     /// # Returns
     ///
-    /// A result containing the updated `GQLIntrospector` instance or an error.
-    pub fn build(mut self) -> Result<Self, Box<dyn Error>> {
-        let mut sb = self.schema;
-        if let Some(introspection_result) =  &self.introspection_result{
-            let implements_iface_map = Self::build_implements_interface_map(introspection_result);
-
-            match &self.introspection_result {
-                Some(introspection_result) => {
-                    for t in &introspection_result.schema.types {
-                        if let Some(name) = &t.name {
-                            if name.starts_with("__") {
-                                continue; // Skip introspection types
-                            }
-                            if let Some(kind) = &t.kind {
-                                match kind.as_str() {
-                                    "OBJECT" => Self::write_object_type(&mut sb, t, &implements_iface_map),
-                                    "ENUM" => Self::write_enum_type(&mut sb, t),
-                                    "SCALAR" => Self::write_scalar_type(&mut sb, t),
-                                    "INTERFACE" => Self::write_interface_type(&mut sb, t),
-                                    "INPUT_OBJECT" => Self::write_input_object_type(&mut sb, t),
-                                    "UNION" => Self::write_union_type(&mut sb, t),
-                                    _ => {
-                                        eprintln!("Unhandled type kind: {}", kind);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                None => return Err(Box::new(GQLInspectorError::new("Introspection result is missing"))),
+    /// A result indicating success or failure.
+    pub fn write_split<P: AsRef<Path>>(&self, dir: P) -> Result<(), Box<dyn Error>> {
+        const FILES: [(&str, &str); 6] = [
+            ("OBJECT", "objects.graphql"),
+            ("INPUT_OBJECT", "inputs.graphql"),
+            ("ENUM", "enums.graphql"),
+            ("SCALAR", "scalars.graphql"),
+            ("INTERFACE", "interfaces.graphql"),
+            ("UNION", "unions.graphql"),
+        ];
+
+        let introspection_result = match &self.introspection_result {
+            Some(introspection_result) => introspection_result,
+            None => return Err(Box::new(GQLInspectorError::new("Introspection result is missing"))),
+        };
+
+        self.clear_warnings();
+        let mut header = String::new();
+        if self.header_comment {
+            self.write_header_comment(&mut header);
+        }
+        let mut schema = header.clone();
+        self.write_schema_block(&mut schema, &introspection_result.schema, true);
+        let mut buffers = FILES.map(|_| header.clone());
+
+        let implements_iface_map = Self::build_implements_interface_map(introspection_result);
+        for t in self.rendered_types(introspection_result) {
+            match FILES.iter().position(|(kind, _)| t.kind.as_deref() == Some(kind)) {
+                Some(i) => self.write_type(&mut buffers[i], t, &implements_iface_map),
+                None => self.warn(format!("Unhandled type kind: {}", t.kind.as_deref().unwrap_or("UNKNOWN"))),
             }
         }
 
-        self.schema = sb;
-        Ok(self)
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("schema.graphql"), schema)?;
+        for ((_, file_name), contents) in FILES.iter().zip(buffers) {
+            fs::write(dir.join(file_name), contents)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the types to render, skipping unnamed types, types not reachable from the
+    /// roots set with `reachable_from`, types emptied by the field filter when
+    /// `skip_emptied_types` is set and, unless included, the built-in introspection types.
+    fn rendered_types<'a>(&'a self, introspection_result: &'a IntrospectionResult) -> impl Iterator<Item = &'a Type> {
+        let reachable = self.reachable_roots.as_ref().map(|roots| Self::reachable_types(introspection_result, roots));
+        introspection_result.schema.types.iter().filter(move |t| match &t.name {
+            Some(name) if name.starts_with("__") && !self.include_introspection_types => false,
+            Some(name) if reachable.as_ref().is_some_and(|reachable| !reachable.contains(name.as_str())) => false,
+            Some(name) if self.skip_emptied_types && self.is_emptied(name, t) => false,
+            Some(_) => self.keeps_type(t),
+            None => false,
+        })
+    }
+
+    /// Returns `true` if the type passes the type filter and carries no excluded directive.
+    fn keeps_type(&self, t: &Type) -> bool {
+        self.type_filter.as_ref().is_none_or(|filter| filter(t)) && !self.excluded_directives.iter().any(|directive| t.has_directive(directive))
+    }
+
+    /// Returns `true` if the field passes the field filter and carries no excluded directive.
+    fn keeps_field(&self, type_name: &str, field: &Field) -> bool {
+        self.field_filter.as_ref().is_none_or(|filter| filter(type_name, field))
+            && !self.excluded_directives.iter().any(|directive| field.has_directive(directive))
+    }
+
+    /// Returns `true` if the type has fields or input fields but the field filter rejects all of them.
+    fn is_emptied(&self, name: &str, t: &Type) -> bool {
+        let fields = t.fields.as_ref().or(t.input_fields.as_ref());
+        fields.is_some_and(|fields| !fields.is_empty() && !fields.iter().any(|field| self.keeps_field(name, field)))
+    }
+
+    /// Collects the names of the types reachable from the given root types.
+    fn reachable_types<'a>(introspection_result: &'a IntrospectionResult, roots: &[RootKind]) -> HashSet<&'a str> {
+        let types = &introspection_result.schema.types;
+        let by_name: HashMap<&str, &Type> = types.iter().filter_map(|t| Some((t.name.as_deref()?, t))).collect();
+        let named = |t: &'a Type| {
+            let mut t = t;
+            while let Some(of_type) = &t.of_type {
+                t = of_type;
+            }
+            t.name.as_deref()
+        };
+
+        let mut reachable = HashSet::new();
+        let mut pending: Vec<&str> = roots
+            .iter()
+            .filter_map(|root| by_name.get_key_value(root.type_name(&introspection_result.schema).as_str()).map(|(name, _)| *name))
+            .collect();
+        while let Some(name) = pending.pop() {
+            let Some(t) = by_name.get(name).filter(|_| reachable.insert(name)) else {
+                continue;
+            };
+            let fields = t.fields.iter().chain(t.input_fields.iter()).flatten();
+            for field in fields {
+                let args = field.args.iter().flatten().filter_map(|arg| arg.field_type.as_ref());
+                pending.extend(field.field_type.iter().chain(args).filter_map(named));
+            }
+            let related = t.interfaces.iter().chain(t.possible_types.iter()).flatten();
+            pending.extend(related.filter_map(|t| t.name.as_deref()));
+            let implementations = types.iter().filter(|other| {
+                other.interfaces.iter().flatten().any(|iface| iface.name.as_deref() == Some(name))
+            });
+            pending.extend(implementations.filter_map(|t| t.name.as_deref()));
+        }
+        reachable
+    }
+
+    fn write_type(&self, sb: &mut String, t: &Type, implements_interface_map: &HashMap<String, Vec<String>>) {
+        if let Some(kind) = &t.kind {
+            match kind.as_str() {
+                "OBJECT" => self.write_object_type(sb, t, implements_interface_map),
+                "ENUM" => self.write_enum_type(sb, t),
+                "SCALAR" => self.write_scalar_type(sb, t),
+                "INTERFACE" => self.write_interface_type(sb, t),
+                "INPUT_OBJECT" => self.write_input_object_type(sb, t),
+                "UNION" => self.write_union_type(sb, t),
+                _ => self.warn(format!("Unhandled type kind: {}", kind)),
+            }
+        }
+    }
+
+    fn write_header_comment(&self, sb: &mut String) {
+        sb.push_str("# Generated by gql-rs");
+        if let Some((url, fetched_at)) = &self.fetched_from {
+            sb.push_str(&format!(" from {} on {}", url, format_timestamp(*fetched_at)));
+        }
+        sb.push_str(" — do not edit\n");
+    }
+
+    /// Writes the `schema { ... }` block, only for non-conventional root names or a schema
+    /// description unless `always`.
+    fn write_schema_block(&self, sb: &mut String, schema: &Schema, always: bool) {
+        let introspected = [&schema.query_type, &schema.mutation_type, &schema.subscription_type]
+            .map(|t| t.as_ref().and_then(|t| t.name.clone()));
+        // Older introspection results may lack the root types; assume the conventional names.
+        let introspected = match introspected {
+            [None, None, None] => ["Query", "Mutation", "Subscription"]
+                .map(|name| schema.types.iter().any(|t| t.name.as_deref() == Some(name)).then(|| name.to_string())),
+            introspected => introspected,
+        };
+        let conventional = introspected
+            .iter()
+            .zip(["Query", "Mutation", "Subscription"])
+            .all(|(name, default)| name.as_deref().is_none_or(|name| name == default));
+
+        let names = match &self.root_names {
+            Some(names) => names,
+            None if conventional && !always && schema.description.is_none() => return,
+            None => &introspected,
+        };
+
+        Self::write_separator(sb);
+        self.write_description(sb, &schema.description, "");
+        sb.push_str("schema {\n");
+        for ((operation, name), kind) in ["query", "mutation", "subscription"].iter().zip(names).zip(RootKind::ALL) {
+            let selected = self.reachable_roots.as_ref().is_none_or(|roots| roots.contains(&kind));
+            if let Some(name) = name.as_ref().filter(|_| selected) {
+                sb.push_str(&format!("  {}: {}\n", operation, name));
+            }
+        }
+        sb.push_str("}\n");
+    }
+
+    /// Separates a declaration from the previous one, if any, with exactly one blank line.
+    fn write_separator(sb: &mut String) {
+        if !sb.is_empty() {
+            sb.push('\n');
+        }
+    }
+
+    /// Writes the description and `keyword name` that start a type definition, or
+    /// `extend keyword name` for types rendered as extensions.
+    fn write_definition(&self, sb: &mut String, keyword: &str, name: &str, description: &Option<String>) {
+        Self::write_separator(sb);
+        if self.extended_types.contains(name) {
+            sb.push_str("extend ");
+        } else {
+            self.write_description(sb, description, "");
+        }
+        sb.push_str(&format!("{} {}", keyword, name));
     }
 
     fn build_implements_interface_map(introspection: &IntrospectionResult) -> HashMap<String, Vec<String>> {
@@ -290,119 +1541,221 @@ impl GQLIntrospector {
         implements_interface_map
     }
 
-    fn write_object_type(sb: &mut String, t: &Type, implements_interface_map: &HashMap<String, Vec<String>> ) {
+    fn write_object_type(&self, sb: &mut String, t: &Type, implements_interface_map: &HashMap<String, Vec<String>> ) {
        
         if let Some(name) = &t.name {
-            sb.push_str(&format!("type {}", name));
+            self.write_definition(sb, "type", name, &t.description);
             if let Some(implements) = implements_interface_map.get(name) {
                 sb.push_str(&format!(" implements {}", implements.join(" & ")));
             }
+            #[cfg(feature = "federation")]
+            Self::write_directives(sb, &t.applied_directives);
             sb.push_str(" {\n");
             if let Some(fields) = &t.fields {
-                for field in fields {
-                    Self::write_field(sb, field);
+                for field in self.unique_fields(name, fields) {
+                    self.write_field(sb, field);
                 }
             }
-            sb.push_str("}\n\n");
+            sb.push_str("}\n");
         }
     }
     
-    fn write_enum_type(sb: &mut String, t: &Type) {
+    fn write_enum_type(&self, sb: &mut String, t: &Type) {
         if let Some(name) = &t.name {
-            sb.push_str(&format!("enum {} {{\n", name));
+            self.write_definition(sb, "enum", name, &t.description);
+            sb.push_str(" {\n");
             if let Some(enum_values) = &t.enum_values {
+                let mut enum_values: Vec<_> = enum_values.iter().collect();
+                if self.sort_enum_values {
+                    enum_values.sort_by(|a, b| a.name.cmp(&b.name));
+                }
                 for value in enum_values {
                     if let Some(value_name) = &value.name {
+                        self.write_description(sb, &value.description, "  ");
                         sb.push_str(&format!("  {}\n", value_name));
                     }
                 }
             }
-            sb.push_str("}\n\n");
+            sb.push_str("}\n");
         }
     }
     
-    fn write_scalar_type(sb: &mut String, t: &Type) {
+    fn write_scalar_type(&self, sb: &mut String, t: &Type) {
         if let Some(name) = &t.name {
-            sb.push_str(&format!("scalar {}\n\n", name));
+            self.write_definition(sb, "scalar", name, &t.description);
+            sb.push('\n');
         }
     }
     
-    fn write_interface_type(sb: &mut String, t: &Type) {
+    fn write_interface_type(&self, sb: &mut String, t: &Type) {
         if let Some(name) = &t.name {
-            sb.push_str(&format!("interface {} {{\n", name));
+            self.write_definition(sb, "interface", name, &t.description);
+            #[cfg(feature = "federation")]
+            Self::write_directives(sb, &t.applied_directives);
+            sb.push_str(" {\n");
             if let Some(fields) = &t.fields {
-                for field in fields {
-                    GQLIntrospector::write_field(sb, field);
+                for field in self.unique_fields(name, fields) {
+                    self.write_field(sb, field);
                 }
             }
-            sb.push_str("}\n\n");
+            sb.push_str("}\n");
         }
     }
     
-    fn write_input_object_type(sb: &mut String, t: &Type) {
+    fn write_input_object_type(&self, sb: &mut String, t: &Type) {
         if let Some(name) = &t.name {
-            sb.push_str(&format!("input {} {{\n", name));
+            self.write_definition(sb, "input", name, &t.description);
+            sb.push_str(" {\n");
             
             if let Some(input_fields) = &t.input_fields {
-                for input_field in input_fields {
-                    if let (Some(input_field_name), Some(field_type)) = (&input_field.name, &input_field.field_type) {
-                        sb.push_str(&format!("  {}: {}\n", input_field_name, Self::format_type(field_type)));
-                    }
+                for input_field in self.unique_fields(name, input_fields) {
+                    self.write_input_field(sb, input_field);
                 }
             }
     
-            sb.push_str("}\n\n");
+            sb.push_str("}\n");
         }
     }
     
-    fn write_union_type(sb: &mut String, t: &Type) {
+    fn write_union_type(&self, sb: &mut String, t: &Type) {
         if let Some(name) = &t.name {
-            sb.push_str(&format!("union {} = ", name));
-            if let Some(possible_types) = &t.possible_types {
-                for (i, possible_type) in possible_types.iter().enumerate() {
-                    if i > 0 {
-                        sb.push_str(" | ");
-                    }
-                    if let Some(possible_type_name) = &possible_type.name {
-                        sb.push_str(possible_type_name);
-                    }
+            self.write_definition(sb, "union", name, &t.description);
+            let members: Vec<_> = t.possible_types.iter().flatten().filter_map(|possible_type| possible_type.name.as_deref()).collect();
+            if !members.is_empty() {
+                sb.push_str(&format!(" = {}", members.join(" | ")));
+            }
+            sb.push('\n');
+        }
+    }
+
+    /// Returns the fields in order, keeping only the first field for each name.
+    fn unique_fields<'a>(&self, type_name: &str, fields: &'a [Field]) -> Vec<&'a Field> {
+        let mut seen = HashSet::new();
+        fields
+            .iter()
+            .filter(|field| match &field.name {
+                Some(name) if !seen.insert(name.as_str()) => {
+                    self.warn(format!("Duplicate field {} on type {} was skipped", name, type_name));
+                    false
                 }
+                _ => self.keeps_field(type_name, field),
+            })
+            .collect()
+    }
+
+    fn write_input_field(&self, sb: &mut String, input_field: &Field) {
+        if let (Some(name), Some(field_type)) = (&input_field.name, &input_field.field_type) {
+            self.write_description(sb, &input_field.description, "  ");
+            sb.push_str(&format!("  {}: {}", name, Self::format_type(field_type)));
+            Self::write_deprecation(sb, input_field.is_deprecated, &input_field.deprecation_reason);
+            #[cfg(feature = "federation")]
+            Self::write_directives(sb, &input_field.applied_directives);
+            sb.push('\n');
+        }
+    }
+
+    fn write_description(&self, sb: &mut String, description: &Option<String>, indent: &str) {
+        if self.strip_descriptions {
+            return;
+        }
+        let Some(description) = description.as_deref().filter(|d| !d.is_empty()) else {
+            return;
+        };
+        // Like graphql-js, single-line descriptions are plain strings (JSON escaping is valid
+        // GraphQL) and multi-line ones are block strings, in which `"""` is escaped as `\\"""`.
+        if !description.contains('\n') {
+            sb.push_str(&format!("{}{}\n", indent, serde_json::Value::from(description)));
+            return;
+        }
+        sb.push_str(&format!("{}\"\"\"\n", indent));
+        for line in description.replace("\"\"\"", "\\\"\"\"").lines() {
+            // Blank lines stay empty rather than carrying the indentation.
+            if !line.is_empty() {
+                sb.push_str(indent);
+            }
+            sb.push_str(line);
+            sb.push('\n');
+        }
+        sb.push_str(&format!("{}\"\"\"\n", indent));
+    }
+
+    fn write_deprecation(sb: &mut String, is_deprecated: Option<bool>, reason: &Option<String>) {
+        if is_deprecated != Some(true) {
+            return;
+        }
+        match reason.as_deref() {
+            Some(reason) if reason != "No longer supported" => {
+                let reason = serde_json::to_string(reason).unwrap_or_default();
+                sb.push_str(&format!(" @deprecated(reason: {})", reason));
             }
-            sb.push_str("\n\n");
+            _ => sb.push_str(" @deprecated"),
         }
     }
 
-    fn write_field(sb: &mut String, field: &Field) {
+    /// Writes applied directives such as `@key` and `@external`; `@deprecated` is written by `write_deprecation`.
+    #[cfg(feature = "federation")]
+    fn write_directives(sb: &mut String, directives: &Option<Vec<AppliedDirective>>) {
+        for directive in directives.iter().flatten() {
+            let Some(name) = directive.name.as_deref().filter(|name| *name != "deprecated") else {
+                continue;
+            };
+            sb.push_str(&format!(" @{}", name));
+            let args: Vec<_> = directive
+                .args
+                .iter()
+                .flatten()
+                .filter_map(|arg| Some(format!("{}: {}", arg.name.as_ref()?, arg.value.as_ref()?)))
+                .collect();
+            if !args.is_empty() {
+                sb.push_str(&format!("({})", args.join(", ")));
+            }
+        }
+    }
+
+    fn write_field(&self, sb: &mut String, field: &Field) {
         if let Some(name) = &field.name {
+            self.write_description(sb, &field.description, "  ");
             sb.push_str(&format!("  {}", name));
             if let Some(args) = &field.args {
-                if args.len() > 0 {
-                    sb.push_str("(");
+                if !args.is_empty() {
+                    sb.push('(');
                     for (i, arg) in args.iter().enumerate() {
                         if i > 0 {
                             sb.push_str(", ");
                         }
                         if let Some(arg_name) = &arg.name {
-                            if let Some(field_type) = &field.field_type {
-                                sb.push_str(&format!("{}: {}", arg_name, Self::format_type(field_type)));
+                            match &arg.field_type {
+                                Some(arg_type) => {
+                                    sb.push_str(&format!("{}: {}", arg_name, Self::format_type(arg_type)));
+                                    Self::write_deprecation(sb, arg.is_deprecated, &arg.deprecation_reason);
+                                }
+                                None => {
+                                    self.warn(format!("Argument {} of field {} has no type", arg_name, name));
+                                    sb.push_str(arg_name);
+                                }
                             }
                         }
                     }
-                    sb.push_str(")");
+                    sb.push(')');
                 }
             }
-            if let Some(field_type) = &field.field_type {
-                sb.push_str(&format!(": {}\n", Self::format_type(&field_type)));
+            match &field.field_type {
+                Some(field_type) => sb.push_str(&format!(": {}", Self::format_type(field_type))),
+                None => self.warn(format!("Field {} has no return type", name)),
             }
+            Self::write_deprecation(sb, field.is_deprecated, &field.deprecation_reason);
+            #[cfg(feature = "federation")]
+            Self::write_directives(sb, &field.applied_directives);
+            sb.push('\n');
         }
     }
 
     fn format_type(t: &Type) -> String {
         if let Some(of_type) = &t.of_type {
             if let Some(kind) = &t.kind {
-                if kind.to_string() == "LIST".to_string() {
+                if kind == "LIST" {
                     return format!("[{}]", Self::format_type(of_type));
-                } else if kind.to_string() == "NON_NULL".to_string() {
+                } else if kind == "NON_NULL" {
                     return format!("{}!", Self::format_type(of_type));
                 }
             }
@@ -417,6 +1770,37 @@ impl GQLIntrospector {
         "".to_string()
     }
     
+    /// Serializes the introspection result back into the standard introspection JSON shape.
+    ///
+    /// The output is `{ "__schema": { ... } }`, as consumed by tools such as GraphiQL
+    /// and graphql-codegen.
+    ///
+    /// # Returns
+    ///
+    /// A result containing the pretty-printed JSON or an error if no introspection has been performed.
+    pub fn to_introspection_json(&self) -> Result<String, Box<dyn Error>> {
+        match &self.introspection_result {
+            Some(introspection_result) => Ok(serde_json::to_string_pretty(introspection_result)?),
+            None => Err(Box::new(GQLInspectorError::new("Introspection result is missing"))),
+        }
+    }
+
+    /// Sets whether `write` and `write_if_changed` create missing parent directories.
+    ///
+    /// Off by default, so that a mistyped path fails instead of creating directories.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether missing parent directories are created.
+    ///
+    /// # Returns
+    ///
+    /// The updated `GQLIntrospector` instance.
+    pub fn create_dirs(mut self, enabled: bool) -> Self {
+        self.create_dirs = enabled;
+        self
+    }
+
     /// Writes the schema to a file.
     ///
     /// # Arguments
@@ -426,24 +1810,109 @@ impl GQLIntrospector {
     /// # Returns
     ///
     /// A result indicating success or failure.
-    pub fn write(self, file_path: &str) -> Result<(), Box<dyn Error>> {
-        if self.schema.len() == 0 {
-            return Err("No introspection result available to write".into());
-        }
+    pub fn write(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        self.prepare_write(file_path)?;
 
         let mut file = File::create(file_path)?;
         io::Write::write_all(&mut file, self.schema.as_bytes())?;
 
         Ok(())
     }
+
+    /// Writes the schema to a file, unless the file already has the same content.
+    ///
+    /// Leaving an unchanged file untouched keeps its modification time, so build
+    /// systems do not rebuild what depends on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - The path of the file to write the schema to.
+    ///
+    /// # Returns
+    ///
+    /// A result containing whether the file was written, or an error.
+    pub fn write_if_changed(&self, file_path: &str) -> Result<bool, Box<dyn Error>> {
+        self.prepare_write(file_path)?;
+
+        let unchanged = fs::read(file_path).is_ok_and(|existing| existing == self.schema.as_bytes());
+        if unchanged {
+            return Ok(false);
+        }
+        self.write(file_path)?;
+        Ok(true)
+    }
+
+    /// Checks that there is a schema to write and creates parent directories if enabled.
+    fn prepare_write(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        if self.schema.is_empty() {
+            let message = match &self.introspection_result {
+                None => "No introspection result available to write",
+                Some(introspection_result) if self.rendered_types(introspection_result).next().is_none() => {
+                    "Introspection succeeded but returned no user-defined types; the schema is empty"
+                }
+                Some(_) => "The schema has not been built; call build before write",
+            };
+            return Err(message.into());
+        }
+
+        let parent = Path::new(file_path).parent().filter(|parent| !parent.as_os_str().is_empty());
+        if let Some(parent) = parent {
+            if !parent.exists() {
+                if !self.create_dirs {
+                    let message = format!("Directory {} does not exist; create it or enable create_dirs", parent.display());
+                    return Err(Box::new(GQLInspectorError::new(&message)));
+                }
+                fs::create_dir_all(parent)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats a time as an RFC 3339 UTC timestamp, e.g. `2024-01-01T00:00:00Z`.
+fn format_timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, secs_of_day) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // Converts days since the epoch to a civil date (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
     use std::collections::HashMap;
     use std::fs;
 
+    fn introspector_from(types: serde_json::Value) -> GQLIntrospector {
+        let introspection_result: IntrospectionResult =
+            serde_json::from_value(json!({ "__schema": { "types": types } })).expect("Invalid introspection fixture");
+
+        GQLIntrospector {
+            introspection_result: Some(introspection_result),
+            ..GQLIntrospector::new()
+        }
+    }
+
     #[test]
     fn test_add() {
         let introspector = GQLIntrospector::new().add("Authorization", "Bearer token");
@@ -454,6 +1923,10 @@ mod tests {
     fn test_build() {
         let introspection_result = IntrospectionResult {
             schema: Schema {
+                description: None,
+                query_type: None,
+                mutation_type: None,
+                subscription_type: None,
                 types: vec![
                     Type {
                         kind: Some("OBJECT".to_string()),
@@ -473,11 +1946,13 @@ mod tests {
                                     enum_values: None,
                                     possible_types: None,
                                     of_type: None,
+                                    applied_directives: None,
                                 }),
                                 default_value: None,
                                 is_deprecated: None,
                                 deprecation_reason: None,
                                 args: None,
+                                applied_directives: None,
                             },
                         ]),
                         input_fields: None,
@@ -485,6 +1960,7 @@ mod tests {
                         enum_values: None,
                         possible_types: None,
                         of_type: None,
+                        applied_directives: None,
                     },
                     Type {
                         kind: Some("OBJECT".to_string()),
@@ -504,11 +1980,13 @@ mod tests {
                                     enum_values: None,
                                     possible_types: None,
                                     of_type: None,
+                                    applied_directives: None,
                                 }),
                                 default_value: None,
                                 is_deprecated: None,
                                 deprecation_reason: None,
                                 args: None,
+                                applied_directives: None,
                             },
                         ]),
                         input_fields: None,
@@ -516,6 +1994,7 @@ mod tests {
                         enum_values: None,
                         possible_types: None,
                         of_type: None,
+                        applied_directives: None,
                     },
                 ],
             },
@@ -525,6 +2004,7 @@ mod tests {
             headers: HashMap::new(),
             introspection_result: Some(introspection_result),
             schema: String::new(),
+            ..GQLIntrospector::new()
         };
 
         let result = introspector.build();
@@ -542,6 +2022,7 @@ mod tests {
             headers: HashMap::new(),
             introspection_result: None,
             schema: schema_content.to_string(),
+            ..GQLIntrospector::new()
         };
 
         let file_path = "./test_output.graphql";
@@ -555,4 +2036,961 @@ mod tests {
         // Cleanup
         fs::remove_file(file_path).expect("Unable to delete file");
     }
+
+    #[test]
+    fn test_object_repeats_interface_fields() {
+        let id = json!({ "name": "id", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } });
+        let friends = json!({
+            "name": "friends",
+            "args": [{ "name": "first", "type": { "kind": "SCALAR", "name": "Int" } }],
+            "type": { "kind": "LIST", "ofType": { "kind": "INTERFACE", "name": "Node" } }
+        });
+        let introspector = introspector_from(json!([
+            { "kind": "INTERFACE", "name": "Node", "fields": [id, friends] },
+            {
+                "kind": "OBJECT",
+                "name": "User",
+                "interfaces": [{ "kind": "INTERFACE", "name": "Node" }],
+                "fields": [id, friends, { "name": "name", "type": { "kind": "SCALAR", "name": "String" } }]
+            },
+        ]));
+
+        let sdl = introspector.to_sdl().unwrap();
+        let inherited = "  id: ID!\n  friends(first: Int): [Node]\n";
+        assert!(sdl.contains(&format!("interface Node {{\n{}}}", inherited)));
+        assert!(sdl.contains(&format!("type User implements Node {{\n{}  name: String\n}}", inherited)));
+    }
+
+    #[test]
+    fn test_warnings() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "User", "fields": [
+                { "name": "id", "type": { "kind": "SCALAR", "name": "ID" } },
+                { "name": "id", "type": { "kind": "SCALAR", "name": "ID" } },
+                { "name": "name" }
+            ] },
+            { "kind": "DIRECTIVE", "name": "Unknown" },
+        ]))
+        .with_verbosity(Verbosity::Quiet);
+
+        let sdl = introspector.to_sdl().unwrap();
+        assert!(sdl.contains("type User {\n  id: ID\n  name\n}"));
+        let expected = [
+            "Duplicate field id on type User was skipped",
+            "Field name has no return type",
+            "Unhandled type kind: DIRECTIVE",
+        ];
+        assert_eq!(introspector.warnings(), expected);
+
+        // Each rendering reports its own warnings rather than accumulating them.
+        introspector.to_sdl().unwrap();
+        assert_eq!(introspector.warnings().len(), expected.len());
+        assert!(introspector_from(json!([])).warnings().is_empty());
+    }
+
+    #[test]
+    fn test_declaration_whitespace() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "User", "description": "A user  \n\n\nwith details ", "fields": [
+                { "name": "id", "type": { "kind": "SCALAR", "name": "ID" } }
+            ] },
+            { "kind": "UNION", "name": "Result", "possibleTypes": [] },
+            { "kind": "SCALAR", "name": "Date" },
+        ]));
+
+        // Descriptions are kept verbatim, including blank lines and trailing spaces (Markdown hard
+        // breaks), while declarations are separated by exactly one blank line.
+        let sdl = introspector.to_sdl().unwrap();
+        assert_eq!(
+            sdl,
+            "\"\"\"\nA user  \n\n\nwith details \n\"\"\"\ntype User {\n  id: ID\n}\n\nunion Result\n\nscalar Date\n"
+        );
+    }
+
+    #[test]
+    fn test_schema_filters() {
+        let internal = json!([{ "name": "internal" }]);
+        let types = json!([
+            { "kind": "OBJECT", "name": "User", "fields": [
+                { "name": "id", "type": { "kind": "SCALAR", "name": "ID" } },
+                { "name": "passwordHash", "type": { "kind": "SCALAR", "name": "String" }, "appliedDirectives": internal },
+                { "name": "debug", "type": { "kind": "OBJECT", "name": "Debug" } }
+            ] },
+            { "kind": "OBJECT", "name": "Debug", "appliedDirectives": internal, "fields": [] },
+            { "kind": "INPUT_OBJECT", "name": "AdminInput", "inputFields": [] },
+        ]);
+
+        let sdl = introspector_from(types.clone()).exclude_directive("internal").to_sdl().unwrap();
+        assert_eq!(sdl, "type User {\n  id: ID\n  debug: Debug\n}\n\ninput AdminInput {\n}\n");
+
+        let sdl = introspector_from(types.clone())
+            .with_type_filter(|t| !t.name.as_deref().unwrap_or_default().starts_with("Admin"))
+            .with_field_filter(|type_name, field| type_name != "User" || field.name.as_deref() == Some("id"))
+            .to_sdl()
+            .unwrap();
+        // The federation feature renders applied directives.
+        #[cfg(not(feature = "federation"))]
+        assert_eq!(sdl, "type User {\n  id: ID\n}\n\ntype Debug {\n}\n");
+        #[cfg(feature = "federation")]
+        assert_eq!(sdl, "type User {\n  id: ID\n}\n\ntype Debug @internal {\n}\n");
+
+        // The directive is excluded on top of the other filters, in any order.
+        let sdl = introspector_from(types)
+            .with_type_filter(|t| !t.name.as_deref().unwrap_or_default().starts_with("Admin"))
+            .exclude_directive("internal")
+            .with_field_filter(|_, field| field.name.as_deref() != Some("debug"))
+            .to_sdl()
+            .unwrap();
+        assert_eq!(sdl, "type User {\n  id: ID\n}\n");
+    }
+
+    #[test]
+    fn test_skip_emptied_types() {
+        let introspector = || {
+            introspector_from(json!([
+                { "kind": "OBJECT", "name": "User", "fields": [
+                    { "name": "id", "type": { "kind": "SCALAR", "name": "ID" } },
+                    { "name": "_version", "type": { "kind": "SCALAR", "name": "Int" } }
+                ] },
+                { "kind": "OBJECT", "name": "Meta", "fields": [{ "name": "_trace", "type": { "kind": "SCALAR", "name": "String" } }] },
+                { "kind": "OBJECT", "name": "Empty", "fields": [] },
+            ]))
+            .with_field_filter(|_, field| !field.name.as_deref().unwrap_or_default().starts_with('_'))
+        };
+
+        assert_eq!(introspector().to_sdl().unwrap(), "type User {\n  id: ID\n}\n\ntype Meta {\n}\n\ntype Empty {\n}\n");
+        assert_eq!(introspector().skip_emptied_types(true).to_sdl().unwrap(), "type User {\n  id: ID\n}\n\ntype Empty {\n}\n");
+    }
+
+    #[test]
+    fn test_to_sdl_is_reusable() {
+        let introspector = introspector_from(json!([
+            { "kind": "SCALAR", "name": "Date" },
+            { "kind": "ENUM", "name": "Role", "enumValues": [{ "name": "ADMIN" }] },
+        ]));
+
+        let first = introspector.to_sdl().unwrap();
+        let second = introspector.to_sdl().unwrap();
+        assert_eq!(first, second);
+        assert!(first.contains("scalar Date"));
+
+        let built = introspector.build().unwrap();
+        assert_eq!(built.schema, first);
+        assert_eq!(built.build().unwrap().schema, first);
+
+        assert!(GQLIntrospector::new().to_sdl().is_err());
+    }
+
+    #[test]
+    fn test_write_field_args_use_their_own_types() {
+        let field: Field = serde_json::from_value(json!({
+            "name": "user",
+            "args": [
+                { "name": "id", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } },
+                { "name": "locale", "type": { "kind": "SCALAR", "name": "String" } },
+                { "name": "lang", "type": { "kind": "SCALAR", "name": "String" }, "isDeprecated": true, "deprecationReason": "Use `locale`" },
+            ],
+            "type": { "kind": "OBJECT", "name": "User" },
+        }))
+        .unwrap();
+
+        let mut sb = String::new();
+        GQLIntrospector::new().write_field(&mut sb, &field);
+        assert_eq!(sb, "  user(id: ID!, locale: String, lang: String @deprecated(reason: \"Use `locale`\")): User\n");
+
+        let untyped: Field = serde_json::from_value(json!({
+            "name": "search",
+            "args": [{ "name": "term", "type": { "kind": "SCALAR", "name": "String" } }],
+        }))
+        .unwrap();
+
+        let mut sb = String::new();
+        GQLIntrospector::new().write_field(&mut sb, &untyped);
+        assert_eq!(sb, "  search(term: String)\n");
+    }
+
+    #[test]
+    fn test_include_introspection_types() {
+        let types = json!([
+            { "kind": "OBJECT", "name": "__Schema", "fields": [] },
+            { "kind": "SCALAR", "name": "Date" },
+        ]);
+
+        let default_sdl = introspector_from(types.clone()).to_sdl().unwrap();
+        assert!(!default_sdl.contains("__Schema"));
+
+        let full_sdl = introspector_from(types).include_introspection_types(true).to_sdl().unwrap();
+        assert!(full_sdl.contains("type __Schema"));
+        assert!(full_sdl.contains("scalar Date"));
+    }
+
+    #[test]
+    fn test_type_kind_helpers() {
+        let introspector = introspector_from(json!([
+            { "kind": "INPUT_OBJECT", "name": "UserInput" },
+            { "kind": "OBJECT", "name": "User" },
+            { "kind": "ENUM", "name": "Role" },
+        ]));
+
+        let input = introspector.get_type("UserInput").unwrap();
+        assert!(input.is_input_object() && input.is_input_type() && !input.is_output_type());
+
+        let object = introspector.get_type("User").unwrap();
+        assert!(object.is_object() && object.is_output_type() && !object.is_input_type());
+
+        let role = introspector.get_type("Role").unwrap();
+        assert!(role.is_enum() && role.is_input_type() && role.is_output_type());
+        assert!(!role.is_scalar() && !role.is_interface() && !role.is_union());
+
+        assert!(introspector.get_type("Missing").is_none());
+    }
+
+    #[test]
+    fn test_input_fields_with_descriptions_and_deprecations() {
+        let introspector = introspector_from(json!([
+            {
+                "kind": "INPUT_OBJECT",
+                "name": "UserInput",
+                "inputFields": [
+                    { "name": "name", "description": "Display name", "type": { "kind": "SCALAR", "name": "String" } },
+                    {
+                        "name": "login",
+                        "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "String" } },
+                        "isDeprecated": true,
+                        "deprecationReason": "Use `name` instead"
+                    },
+                    { "name": "age", "type": { "kind": "SCALAR", "name": "Int" }, "isDeprecated": false },
+                ]
+            },
+        ]));
+
+        assert_eq!(
+            introspector.to_sdl().unwrap(),
+            "input UserInput {\n  \"Display name\"\n  name: String\n  login: String! @deprecated(reason: \"Use `name` instead\")\n  age: Int\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_to_introspection_json() {
+        let introspector = introspector_from(json!([
+            {
+                "kind": "INPUT_OBJECT",
+                "name": "UserInput",
+                "inputFields": [{ "name": "name", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "String" } } }]
+            },
+        ]));
+
+        let exported: serde_json::Value = serde_json::from_str(&introspector.to_introspection_json().unwrap()).unwrap();
+        let input = &exported["__schema"]["types"][0];
+        assert_eq!(input["name"], json!("UserInput"));
+        assert_eq!(input["inputFields"][0]["type"]["ofType"]["name"], json!("String"));
+
+        let reparsed: IntrospectionResult = serde_json::from_value(exported).unwrap();
+        assert_eq!(reparsed.schema.types.len(), 1);
+        assert!(GQLIntrospector::new().to_introspection_json().is_err());
+    }
+
+    #[test]
+    fn test_duplicate_fields_are_skipped() {
+        let id = json!({ "name": "id", "type": { "kind": "SCALAR", "name": "ID" } });
+        let introspector = introspector_from(json!([
+            {
+                "kind": "OBJECT",
+                "name": "User",
+                "fields": [id, { "name": "name", "type": { "kind": "SCALAR", "name": "String" } }, id]
+            },
+            { "kind": "INPUT_OBJECT", "name": "UserFilter", "inputFields": [id, id] },
+        ]));
+
+        assert_eq!(
+            introspector.to_sdl().unwrap(),
+            "type User {\n  id: ID\n  name: String\n}\n\ninput UserFilter {\n  id: ID\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_no_descriptions() {
+        let types = json!([
+            {
+                "kind": "OBJECT",
+                "name": "User",
+                "fields": [{ "name": "name", "description": "Display name", "type": { "kind": "SCALAR", "name": "String" } }]
+            },
+        ]);
+
+        assert!(introspector_from(types.clone()).to_sdl().unwrap().contains("Display name"));
+        assert_eq!(
+            introspector_from(types).no_descriptions().to_sdl().unwrap(),
+            "type User {\n  name: String\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_from_env() {
+        env::remove_var("GQL_ENDPOINT");
+        assert!(GQLIntrospector::from_env().is_err());
+
+        env::set_var("GQL_ENDPOINT", "https://api.example.com/graphql");
+        env::set_var("GQL_AUTH_TOKEN", "secret");
+        env::set_var("GQL_USER_AGENT", "gql-rs-ci");
+        let introspector = GQLIntrospector::from_env().unwrap();
+        for name in ["GQL_ENDPOINT", "GQL_AUTH_TOKEN", "GQL_USER_AGENT"] {
+            env::remove_var(name);
+        }
+
+        assert_eq!(introspector.endpoint(), Some("https://api.example.com/graphql"));
+        assert_eq!(introspector.headers.get("Authorization"), Some(&"Bearer secret".to_string()));
+        assert_eq!(introspector.headers.get("User-Agent"), Some(&"gql-rs-ci".to_string()));
+    }
+
+    #[test]
+    fn test_client_info() {
+        let introspector = GQLIntrospector::new().with_client_info("gql-rs", "1.2.0");
+        assert_eq!(introspector.headers.get("apollographql-client-name"), Some(&"gql-rs".to_string()));
+        assert_eq!(introspector.headers.get("apollographql-client-version"), Some(&"1.2.0".to_string()));
+    }
+
+    #[cfg(feature = "federation")]
+    #[test]
+    fn test_federation_directives() {
+        let introspector = introspector_from(json!([
+            {
+                "kind": "OBJECT",
+                "name": "User",
+                "appliedDirectives": [{ "name": "key", "args": [{ "name": "fields", "value": "\"id\"" }] }],
+                "fields": [
+                    { "name": "id", "type": { "kind": "SCALAR", "name": "ID" } },
+                    {
+                        "name": "email",
+                        "type": { "kind": "SCALAR", "name": "String" },
+                        "isDeprecated": true,
+                        "appliedDirectives": [{ "name": "external" }, { "name": "deprecated" }, { "name": "tag", "args": [{ "name": "name", "value": "\"private\"" }] }]
+                    },
+                ]
+            },
+            { "kind": "OBJECT", "name": "Post", "fields": [{ "name": "id", "type": { "kind": "SCALAR", "name": "ID" } }] },
+        ]));
+
+        let sdl = introspector.to_sdl().unwrap();
+        assert!(sdl.contains("type User @key(fields: \"id\") {\n"));
+        assert!(sdl.contains("  email: String @deprecated @external @tag(name: \"private\")\n"));
+        assert!(sdl.contains("type Post {\n  id: ID\n}"));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "Query", "fields": [{ "name": "users", "type": { "kind": "OBJECT", "name": "User" } }] },
+            { "kind": "OBJECT", "name": "User", "fields": [{ "name": "id", "type": { "kind": "SCALAR", "name": "ID" } }] },
+            { "kind": "SCALAR", "name": "Date" },
+        ]));
+        let other: IntrospectionResult = serde_json::from_value(json!({ "__schema": { "types": [
+            { "kind": "OBJECT", "name": "Query", "fields": [{ "name": "products", "type": { "kind": "OBJECT", "name": "Product" } }] },
+            { "kind": "OBJECT", "name": "User", "fields": [{ "name": "id", "type": { "kind": "SCALAR", "name": "String" } }] },
+            { "kind": "ENUM", "name": "Date", "enumValues": [] },
+            { "kind": "OBJECT", "name": "Product", "fields": [] },
+        ] } }))
+        .unwrap();
+
+        let conflicts = introspector.merge(other);
+        assert_eq!(conflicts, vec!["Field User.id differs: ID vs String", "Type Date is defined as both SCALAR and ENUM"]);
+
+        let sdl = introspector.to_sdl().unwrap();
+        assert!(sdl.contains("type Query {\n  users: User\n  products: Product\n}"));
+        assert!(sdl.contains("type User {\n  id: ID\n}"));
+        assert!(sdl.contains("scalar Date"));
+        assert!(sdl.contains("type Product"));
+    }
+
+    #[test]
+    fn test_fields_of() {
+        let introspector = introspector_from(json!([
+            {
+                "kind": "OBJECT",
+                "name": "User",
+                "fields": [
+                    { "name": "id", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } },
+                    {
+                        "name": "friends",
+                        "type": { "kind": "NON_NULL", "ofType": { "kind": "LIST", "ofType": { "kind": "NON_NULL", "ofType": { "kind": "OBJECT", "name": "User" } } } }
+                    },
+                ]
+            },
+            { "kind": "INPUT_OBJECT", "name": "UserInput", "inputFields": [{ "name": "name", "type": { "kind": "SCALAR", "name": "String" } }] },
+        ]));
+
+        assert_eq!(
+            introspector.fields_of("User"),
+            vec![("id".to_string(), "ID!".to_string()), ("friends".to_string(), "[User!]!".to_string())]
+        );
+        assert_eq!(introspector.fields_of("UserInput"), vec![("name".to_string(), "String".to_string())]);
+        assert!(introspector.fields_of("Missing").is_empty());
+    }
+
+    #[test]
+    fn test_arguments_of() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "Query", "fields": [{
+                "name": "users",
+                "type": { "kind": "LIST", "ofType": { "kind": "OBJECT", "name": "User" } },
+                "args": [
+                    { "name": "ids", "description": "The users to fetch",
+                      "type": { "kind": "NON_NULL", "ofType": { "kind": "LIST", "ofType": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } } } },
+                    { "name": "first", "type": { "kind": "SCALAR", "name": "Int" }, "defaultValue": "10" }
+                ]
+            }] },
+        ]));
+
+        assert_eq!(
+            introspector.arguments_of("Query", "users"),
+            vec![
+                ArgInfo {
+                    name: "ids".to_string(),
+                    type_name: "[ID!]!".to_string(),
+                    default_value: None,
+                    description: Some("The users to fetch".to_string()),
+                },
+                ArgInfo { name: "first".to_string(), type_name: "Int".to_string(), default_value: Some("10".to_string()), description: None },
+            ]
+        );
+        assert!(introspector.arguments_of("Query", "missing").is_empty());
+        assert!(introspector.arguments_of("Missing", "users").is_empty());
+    }
+
+    #[test]
+    fn test_typescript_for_query() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "Query", "fields": [
+                { "name": "viewer", "type": { "kind": "NON_NULL", "ofType": { "kind": "OBJECT", "name": "User" } } },
+                { "name": "node", "type": { "kind": "INTERFACE", "name": "Node" } }
+            ] },
+            { "kind": "INTERFACE", "name": "Node", "fields": [
+                { "name": "id", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } }
+            ] },
+            { "kind": "OBJECT", "name": "User", "fields": [
+                { "name": "id", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } },
+                { "name": "name", "type": { "kind": "SCALAR", "name": "String" } },
+                { "name": "role", "type": { "kind": "ENUM", "name": "Role" } },
+                { "name": "friends", "type": { "kind": "NON_NULL", "ofType": { "kind": "LIST", "ofType": { "kind": "OBJECT", "name": "User" } } } }
+            ] },
+            { "kind": "ENUM", "name": "Role", "enumValues": [{ "name": "ADMIN" }, { "name": "MEMBER" }] },
+        ]));
+
+        let query = r#"
+            query Viewer($id: ID!) {
+                me: viewer { ...UserParts friends { name } }
+                node(id: $id) { ... on User { id name } id }
+                me: viewer { friends { id } }
+            }
+            fragment UserParts on User { id role }
+        "#;
+        assert_eq!(
+            introspector.typescript_for_query(query).unwrap(),
+            r#"export type ViewerResult = {
+  me: {
+    id: string;
+    role: "ADMIN" | "MEMBER" | null;
+    friends: Array<{
+      name: string | null;
+      id: string;
+    } | null>;
+  };
+  node: {
+    id: string;
+    name?: string | null;
+  } | null;
+};
+"#
+        );
+
+        let err = introspector.typescript_for_query("{ viewer { email } }").unwrap_err();
+        assert_eq!(err.to_string(), "Unknown field email on type User");
+        assert!(introspector.typescript_for_query("{ viewer }").is_err());
+    }
+
+    #[test]
+    fn test_interfaces_of() {
+        let introspector = introspector_from(json!([
+            { "kind": "INTERFACE", "name": "Node" },
+            { "kind": "INTERFACE", "name": "Timestamped" },
+            {
+                "kind": "OBJECT",
+                "name": "User",
+                "interfaces": [{ "kind": "INTERFACE", "name": "Node" }, { "kind": "INTERFACE", "name": "Timestamped" }]
+            },
+            { "kind": "OBJECT", "name": "Query" },
+        ]));
+
+        assert_eq!(introspector.interfaces_of("User"), vec!["Node".to_string(), "Timestamped".to_string()]);
+        assert!(introspector.interfaces_of("Query").is_empty());
+        assert!(introspector.interfaces_of("Missing").is_empty());
+    }
+
+    #[test]
+    fn test_reachable_from() {
+        let object = |name: &str| json!({ "kind": "OBJECT", "name": name });
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "Query", "fields": [
+                { "name": "user", "type": object("User") },
+                { "name": "posts", "type": { "kind": "LIST", "ofType": object("Post") } }
+            ] },
+            { "kind": "OBJECT", "name": "Mutation", "fields": [
+                { "name": "createUser", "type": object("User"),
+                  "args": [{ "name": "input", "type": { "kind": "NON_NULL", "ofType": { "kind": "INPUT_OBJECT", "name": "CreateUserInput" } } }] }
+            ] },
+            { "kind": "OBJECT", "name": "Subscription", "fields": [{ "name": "events", "type": { "kind": "UNION", "name": "Event" } }] },
+            { "kind": "OBJECT", "name": "User", "fields": [{ "name": "id", "type": { "kind": "SCALAR", "name": "ID" } }] },
+            { "kind": "OBJECT", "name": "Post", "fields": [{ "name": "id", "type": { "kind": "SCALAR", "name": "ID" } }] },
+            { "kind": "INPUT_OBJECT", "name": "CreateUserInput", "inputFields": [{ "name": "role", "type": { "kind": "ENUM", "name": "Role" } }] },
+            { "kind": "ENUM", "name": "Role", "enumValues": [{ "name": "ADMIN" }] },
+            { "kind": "UNION", "name": "Event", "possibleTypes": [object("UserCreated")] },
+            { "kind": "OBJECT", "name": "UserCreated", "fields": [{ "name": "user", "type": object("User") }] },
+        ]))
+        .reachable_from(&[RootKind::Mutation, RootKind::Subscription]);
+
+        let names: Vec<_> = introspector.model().objects.into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["Mutation", "Subscription", "User", "UserCreated"]);
+        let sdl = introspector.to_sdl().unwrap();
+        assert!(sdl.contains("input CreateUserInput") && sdl.contains("enum Role") && sdl.contains("union Event"));
+        assert!(!sdl.contains("type Query") && !sdl.contains("type Post"));
+    }
+
+    #[test]
+    fn test_introspection_timeout() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+        // Accepts the connection but never answers.
+        let handle = std::thread::spawn(move || listener.accept().map(|(stream, _)| stream));
+
+        let err = GQLIntrospector::new().with_timeout(Duration::from_millis(200)).get_schema(&url).unwrap_err();
+        assert!(matches!(err.downcast_ref::<gqlclient::RequestError>(), Some(gqlclient::RequestError::Timeout(_))));
+        drop(handle.join());
+    }
+
+    #[test]
+    fn test_introspection_cancellation() {
+        let token = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let err = GQLIntrospector::new().with_cancellation(token).get_schema("http://127.0.0.1:9/graphql").unwrap_err();
+        assert!(matches!(err.downcast_ref::<gqlclient::RequestError>(), Some(gqlclient::RequestError::Cancelled)));
+    }
+
+    /// Answers a single request on a local port with `status`, `content_type` and `body`,
+    /// returning the URL and a handle yielding the raw request.
+    fn serve_once(status: &str, content_type: &str, body: String) -> (String, std::thread::JoinHandle<String>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+        let response = format!("HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n{}", status, content_type, body.len(), body);
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            // Every request body is a JSON object, so the request is complete at its closing brace.
+            while !String::from_utf8_lossy(&request).ends_with('}') {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_deprecated_only() {
+        let body = json!({ "data": { "__schema": { "types": [
+            { "name": "User", "fields": [
+                { "name": "id", "isDeprecated": false },
+                { "name": "login", "isDeprecated": true, "deprecationReason": "Use `handle`" }
+            ], "enumValues": null },
+            { "name": "Role", "fields": null, "enumValues": [{ "name": "ROOT", "isDeprecated": true }] }
+        ] } } });
+        let (url, handle) = serve_once("200 OK", "application/json", body.to_string());
+
+        let deprecations = GQLIntrospector::new().deprecated_only(&url).unwrap();
+        assert_eq!(
+            deprecations,
+            vec![
+                Deprecation { type_name: "User".to_string(), name: "login".to_string(), reason: Some("Use `handle`".to_string()) },
+                Deprecation { type_name: "Role".to_string(), name: "ROOT".to_string(), reason: None },
+            ]
+        );
+        let request = handle.join().unwrap();
+        assert!(request.contains("enumValues(includeDeprecated: true) { name isDeprecated deprecationReason }"));
+        assert!(!request.contains("ofType"));
+    }
+
+    #[test]
+    fn test_ping() {
+        let ping = |status: &str, content_type: &str, body: &str| {
+            let (url, handle) = serve_once(status, content_type, body.to_string());
+            let result = GQLIntrospector::new().ping(&url).map_err(|err| err.to_string().replacen(&url, "url", 1));
+            assert!(handle.join().unwrap().contains("{ __typename }"));
+            result
+        };
+
+        assert!(ping("200 OK", "application/json", r#"{"data":{"__typename":"Query"}}"#).is_ok());
+        assert!(ping("401 Unauthorized", "text/plain", "denied").unwrap_err().starts_with("url rejected the request as unauthorized"));
+        assert!(ping("200 OK", "text/html", "<html></html>").unwrap_err().starts_with("url did not return a GraphQL response"));
+        assert!(ping("404 Not Found", "text/html", "<html></html>").unwrap_err().starts_with("url did not return a GraphQL response"));
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+        drop(listener);
+        assert!(GQLIntrospector::new().ping(&url).unwrap_err().to_string().contains("is unreachable"));
+    }
+
+    #[test]
+    fn test_description_strings() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "User", "description": "A user", "fields": [
+                { "name": "bio", "description": "Free text.\nMay span lines.", "type": { "kind": "SCALAR", "name": "String" } },
+                { "name": "quote", "description": "Wrap in \"\"\" or \\", "type": { "kind": "SCALAR", "name": "String" } },
+                { "name": "raw", "description": "Starts with \"\"\"\nand continues", "type": { "kind": "SCALAR", "name": "String" } }
+            ] },
+        ]));
+
+        assert_eq!(
+            introspector.to_sdl().unwrap(),
+            r#""A user"
+type User {
+  """
+  Free text.
+  May span lines.
+  """
+  bio: String
+  "Wrap in \"\"\" or \\"
+  quote: String
+  """
+  Starts with \"""
+  and continues
+  """
+  raw: String
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_as_extension() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "User", "description": "A user", "interfaces": [{ "kind": "INTERFACE", "name": "Node" }],
+              "fields": [{ "name": "avatar", "type": { "kind": "SCALAR", "name": "String" } }] },
+            { "kind": "ENUM", "name": "Role", "description": "A role", "enumValues": [{ "name": "ADMIN" }] },
+        ]))
+        .as_extension(&["User"]);
+
+        assert_eq!(
+            introspector.to_sdl().unwrap(),
+            "extend type User implements Node {\n  avatar: String\n}\n\n\"A role\"\nenum Role {\n  ADMIN\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_to_bytes_and_write_to() {
+        let introspector = introspector_from(json!([{ "kind": "SCALAR", "name": "Date" }]));
+        assert_eq!(introspector.to_bytes().unwrap(), b"scalar Date\n");
+
+        let mut out = Vec::new();
+        introspector.write_to(&mut out).unwrap();
+        assert_eq!(out, b"scalar Date\n");
+        assert!(GQLIntrospector::new().write_to(&mut out).is_err());
+    }
+
+    #[test]
+    fn test_sort_enum_values() {
+        let types = json!([{ "kind": "ENUM", "name": "Role", "enumValues": [{ "name": "USER" }, { "name": "ADMIN" }, { "name": "GUEST" }] }]);
+        assert_eq!(introspector_from(types.clone()).to_sdl().unwrap(), "enum Role {\n  USER\n  ADMIN\n  GUEST\n}\n");
+        assert_eq!(introspector_from(types).sort_enum_values(true).to_sdl().unwrap(), "enum Role {\n  ADMIN\n  GUEST\n  USER\n}\n");
+    }
+
+    #[test]
+    fn test_group_by_kind() {
+        let types = json!([
+            { "kind": "OBJECT", "name": "User", "fields": [] },
+            { "kind": "ENUM", "name": "Role", "enumValues": [{ "name": "ADMIN" }] },
+            { "kind": "SCALAR", "name": "Date" },
+            { "kind": "OBJECT", "name": "Post", "fields": [] },
+            { "kind": "UNION", "name": "Result", "possibleTypes": [{ "kind": "OBJECT", "name": "User" }] },
+            { "kind": "SCALAR", "name": "URL" },
+        ]);
+        let declarations = |introspector: GQLIntrospector| -> Vec<String> {
+            let sdl = introspector.to_sdl().unwrap();
+            sdl.lines().filter(|line| !line.is_empty() && !line.starts_with(' ') && line != &"}").map(str::to_string).collect()
+        };
+
+        assert_eq!(
+            declarations(introspector_from(types.clone()).group_by_kind()),
+            ["scalar Date", "scalar URL", "enum Role {", "type User {", "type Post {", "union Result = User"]
+        );
+        assert_eq!(
+            declarations(introspector_from(types).with_kind_order(&["UNION", "OBJECT"])),
+            ["union Result = User", "type User {", "type Post {", "enum Role {", "scalar Date", "scalar URL"]
+        );
+    }
+
+    #[test]
+    fn test_check_references() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "User", "interfaces": [{ "kind": "INTERFACE", "name": "Node" }], "fields": [
+                { "name": "id", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } },
+                { "name": "friends", "type": { "kind": "LIST", "ofType": { "kind": "OBJECT", "name": "Friend" } },
+                  "args": [{ "name": "filter", "type": { "kind": "INPUT_OBJECT", "name": "FriendFilter" } }] }
+            ] },
+            { "kind": "INPUT_OBJECT", "name": "UserInput", "inputFields": [
+                { "name": "role", "type": { "kind": "ENUM", "name": "Role" } }
+            ] },
+            { "kind": "UNION", "name": "Result", "possibleTypes": [{ "kind": "OBJECT", "name": "User" }, { "kind": "OBJECT", "name": "Error" }] },
+        ]));
+
+        assert_eq!(
+            introspector.check_references(),
+            vec![
+                "User.friends references undefined type Friend",
+                "User.friends(filter) references undefined type FriendFilter",
+                "User implements undefined interface Node",
+                "UserInput.role references undefined type Role",
+                "Union Result has undefined member Error",
+            ]
+        );
+        assert!(introspector_from(json!([{ "kind": "SCALAR", "name": "Date" }])).check_references().is_empty());
+    }
+
+    #[test]
+    fn test_header_comment() {
+        let types = json!([{ "kind": "SCALAR", "name": "Date" }]);
+        assert_eq!(introspector_from(types.clone()).to_sdl().unwrap(), "scalar Date\n");
+
+        let mut introspector = introspector_from(types).with_header_comment(true);
+        introspector.fetched_from = Some((
+            "https://api.example.com/graphql".to_string(),
+            UNIX_EPOCH + std::time::Duration::from_secs(1_704_067_200),
+        ));
+        assert_eq!(
+            introspector.to_sdl().unwrap(),
+            "# Generated by gql-rs from https://api.example.com/graphql on 2024-01-01T00:00:00Z — do not edit\n\nscalar Date\n"
+        );
+        assert_eq!(format_timestamp(UNIX_EPOCH + std::time::Duration::from_secs(951_825_661)), "2000-02-29T12:01:01Z");
+    }
+
+    #[test]
+    fn test_null_schema() {
+        let data: IntrospectionData = serde_json::from_str(r#"{"__schema":null}"#).unwrap();
+        let err = data.into_result().unwrap_err();
+        assert_eq!(err.to_string(), "server returned no schema; introspection may be restricted");
+
+        let data: IntrospectionData = serde_json::from_str(r#"{"__schema":{"types":[]}}"#).unwrap();
+        assert!(data.into_result().unwrap().schema.types.is_empty());
+    }
+
+    #[test]
+    fn test_schema_block() {
+        let fixture = |query_type: &str| {
+            let introspection_result: IntrospectionResult = serde_json::from_value(json!({ "__schema": {
+                "queryType": { "name": query_type },
+                "types": [{ "kind": "OBJECT", "name": query_type, "fields": [] }]
+            }}))
+            .unwrap();
+            GQLIntrospector { introspection_result: Some(introspection_result), ..GQLIntrospector::new() }
+        };
+
+        assert_eq!(fixture("Query").to_sdl().unwrap(), "type Query {\n}\n");
+        assert_eq!(fixture("QueryRoot").to_sdl().unwrap(), "schema {\n  query: QueryRoot\n}\n\ntype QueryRoot {\n}\n");
+        assert_eq!(
+            fixture("Query").with_root_names("Root", Some("MutationRoot"), None).to_sdl().unwrap(),
+            "schema {\n  query: Root\n  mutation: MutationRoot\n}\n\ntype Query {\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_schema_description() {
+        let introspection_result: IntrospectionResult = serde_json::from_value(json!({ "__schema": {
+            "description": "The public API",
+            "queryType": { "name": "Query" },
+            "types": [{ "kind": "OBJECT", "name": "Query", "fields": [] }]
+        }}))
+        .unwrap();
+        let introspector = GQLIntrospector { introspection_result: Some(introspection_result), ..GQLIntrospector::new() };
+        assert_eq!(introspector.to_sdl().unwrap(), "\"The public API\"\nschema {\n  query: Query\n}\n\ntype Query {\n}\n");
+
+        let query = IntrospectionQueryOptions { include_schema_description: true, ..Default::default() }.to_query();
+        assert!(query.contains("    __schema {\n        description queryType { name }"));
+        assert!(!IntrospectionQueryOptions::default().to_query().contains("__schema {\n        description"));
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let field = |name: &str| json!({ "name": name, "type": { "kind": "SCALAR", "name": "Int" } });
+        let introspection_result: IntrospectionResult = serde_json::from_value(json!({ "__schema": {
+            "queryType": { "name": "QueryRoot" },
+            "mutationType": { "name": "MutationRoot" },
+            "types": [
+                { "kind": "OBJECT", "name": "QueryRoot", "fields": [field("a"), field("b")] },
+                { "kind": "OBJECT", "name": "MutationRoot", "fields": [field("c")] },
+                { "kind": "OBJECT", "name": "__Schema", "fields": [] }
+            ]
+        }}))
+        .unwrap();
+        let introspector = GQLIntrospector { introspection_result: Some(introspection_result), ..GQLIntrospector::new() };
+
+        assert_eq!(
+            introspector.capabilities(),
+            Capabilities {
+                has_mutations: true,
+                has_subscriptions: false,
+                query_field_count: 2,
+                mutation_field_count: 1,
+                subscription_field_count: 0,
+                type_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_write_split() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "Query", "fields": [{ "name": "a", "type": { "kind": "SCALAR", "name": "Date" } }] },
+            { "kind": "SCALAR", "name": "Date" },
+            { "kind": "ENUM", "name": "Color", "enumValues": [{ "name": "RED" }] }
+        ]));
+        let dir = std::env::temp_dir().join(format!("gql-rs-write-split-{}", std::process::id()));
+        introspector.write_split(&dir).unwrap();
+
+        let read = |file: &str| fs::read_to_string(dir.join(file)).unwrap();
+        assert_eq!(read("schema.graphql"), "schema {\n  query: Query\n}\n");
+        assert_eq!(read("objects.graphql"), "type Query {\n  a: Date\n}\n");
+        assert_eq!(read("scalars.graphql"), "scalar Date\n");
+        assert!(read("enums.graphql").starts_with("enum Color {"));
+        assert_eq!(read("unions.graphql"), "");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_render() {
+        struct TypeCount;
+
+        impl SchemaFormatter for TypeCount {
+            fn format(&self, introspection_result: &IntrospectionResult) -> String {
+                introspection_result.schema.types.len().to_string()
+            }
+        }
+
+        let introspector = introspector_from(json!([{ "kind": "SCALAR", "name": "Date", "description": "A date" }]));
+        assert_eq!(introspector.render(TypeCount).unwrap(), "1");
+        assert_eq!(introspector.render(SdlFormatter).unwrap(), introspector.to_sdl().unwrap());
+        assert_eq!(introspector.render(JsonFormatter).unwrap(), introspector.to_introspection_json().unwrap());
+        assert!(GQLIntrospector::new().render(TypeCount).is_err());
+    }
+
+    #[test]
+    fn test_model() {
+        let introspector = introspector_from(json!([
+            { "kind": "OBJECT", "name": "User", "interfaces": [{ "kind": "INTERFACE", "name": "Node" }], "fields": [{
+                "name": "friends",
+                "args": [{ "name": "first", "type": { "kind": "SCALAR", "name": "Int" }, "defaultValue": "10" }],
+                "type": { "kind": "NON_NULL", "ofType": { "kind": "LIST", "ofType": { "kind": "OBJECT", "name": "User" } } },
+                "isDeprecated": true
+            }] },
+            { "kind": "UNION", "name": "Result", "possibleTypes": [{ "kind": "OBJECT", "name": "User" }] },
+            { "kind": "OBJECT", "name": "__Type", "fields": [] }
+        ]));
+        let model = introspector.model();
+
+        assert_eq!(model.objects.len(), 1);
+        let user = &model.objects[0];
+        assert_eq!(user.interfaces, vec!["Node".to_string()]);
+        let friends = &user.fields[0];
+        assert_eq!(
+            friends.field_type,
+            TypeRef::NonNull(Box::new(TypeRef::List(Box::new(TypeRef::Named("User".to_string())))))
+        );
+        assert_eq!(friends.field_type.to_string(), "[User]!");
+        assert_eq!(friends.field_type.named_type(), "User");
+        assert!(friends.is_deprecated);
+        assert_eq!(friends.args[0].value_type, TypeRef::Named("Int".to_string()));
+        assert_eq!(friends.args[0].default_value.as_deref(), Some("10"));
+        assert_eq!(model.unions[0].members, vec!["User".to_string()]);
+    }
+
+    #[test]
+    fn test_with_signer() {
+        use std::sync::Mutex;
+
+        let signed = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&signed);
+        let mut introspector = GQLIntrospector::new().with_signer("X-Request-Signature", move |bytes| {
+            recorded.lock().unwrap().extend_from_slice(bytes);
+            "signature".to_string()
+        });
+
+        // Nothing listens on port 1, but the body is signed before it is sent.
+        assert!(introspector.fetch("http://127.0.0.1:1/graphql").is_err());
+        let body = String::from_utf8(signed.lock().unwrap().clone()).unwrap();
+        assert!(body.starts_with(r#"{"query":"query {\n    __schema {"#));
+
+        // Under GET the encoded query string is signed instead, since there is no body.
+        signed.lock().unwrap().clear();
+        introspector = introspector.use_get(true);
+        assert!(introspector.fetch("http://127.0.0.1:1/graphql").is_err());
+        let query = String::from_utf8(signed.lock().unwrap().clone()).unwrap();
+        assert!(query.starts_with("query=query+%7B%0A"), "{}", query);
+    }
+
+    #[test]
+    fn test_write_empty_schema() {
+        let file_path = "./test_write_empty_schema.graphql";
+
+        let err = GQLIntrospector::new().write(file_path).unwrap_err();
+        assert_eq!(err.to_string(), "No introspection result available to write");
+
+        let builtin_only = json!([{ "kind": "OBJECT", "name": "__Schema", "fields": [] }]);
+        let err = introspector_from(builtin_only).build().unwrap().write(file_path).unwrap_err();
+        assert_eq!(err.to_string(), "Introspection succeeded but returned no user-defined types; the schema is empty");
+
+        let err = introspector_from(json!([{ "kind": "SCALAR", "name": "Date" }])).write(file_path).unwrap_err();
+        assert_eq!(err.to_string(), "The schema has not been built; call build before write");
+        assert!(!Path::new(file_path).exists());
+    }
+
+    #[test]
+    fn test_write_if_changed_and_create_dirs() {
+        let dir = std::env::temp_dir().join(format!("gql-rs-write-if-changed-{}", std::process::id()));
+        let file_path = dir.join("nested/schema.graphql");
+        let file_path = file_path.to_str().unwrap();
+        let introspector = GQLIntrospector {
+            schema: "scalar Date\n\n".to_string(),
+            ..GQLIntrospector::new()
+        };
+
+        let err = introspector.write(file_path).unwrap_err();
+        assert!(err.to_string().ends_with("does not exist; create it or enable create_dirs"));
+
+        let introspector = introspector.create_dirs(true);
+        assert!(introspector.write_if_changed(file_path).unwrap());
+        assert!(!introspector.write_if_changed(file_path).unwrap());
+        assert_eq!(fs::read_to_string(file_path).unwrap(), "scalar Date\n\n");
+
+        let changed = GQLIntrospector {
+            schema: "scalar JSON\n\n".to_string(),
+            ..GQLIntrospector::new()
+        };
+        assert!(changed.write_if_changed(file_path).unwrap());
+        assert_eq!(fs::read_to_string(file_path).unwrap(), "scalar JSON\n\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_update_snapshot() {
+        let snapshot_path = std::env::temp_dir().join(format!("gql-rs-snapshot-{}.json", std::process::id()));
+        let _ = fs::remove_file(&snapshot_path);
+
+        let first = introspector_from(json!([{ "kind": "SCALAR", "name": "Date" }]));
+        assert!(first.update_snapshot(&snapshot_path).unwrap().is_empty());
+
+        let second = introspector_from(json!([{ "kind": "SCALAR", "name": "Date" }, { "kind": "SCALAR", "name": "JSON" }]));
+        assert_eq!(second.update_snapshot(&snapshot_path).unwrap().added, vec!["JSON"]);
+        assert!(second.update_snapshot(&snapshot_path).unwrap().is_empty());
+        fs::remove_file(&snapshot_path).unwrap();
+    }
 }
@@ -0,0 +1,240 @@
+use crate::{FieldDef, IntrospectionResult, SchemaFormatter, SchemaModel};
+
+/// Generates Markdown reference documentation for the schema.
+///
+/// Each kind of type gets its own section, with the fields, values and members of
+/// every type listed under it. Deprecation reasons are emitted as-is, so references
+/// such as "Use `newField` instead" render as code.
+///
+/// ```
+/// use gql_introspector::MarkdownFormatter;
+///
+/// let formatter = MarkdownFormatter::default().with_deprecated_appendix(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownFormatter {
+    /// Appends a "Deprecated API" section listing every deprecated field, argument, input
+    /// field and enum value with its owning type and reason, as a single migration checklist.
+    pub deprecated_appendix: bool,
+}
+
+impl MarkdownFormatter {
+    /// Sets whether a "Deprecated API" appendix is generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the appendix is generated.
+    pub fn with_deprecated_appendix(mut self, enabled: bool) -> Self {
+        self.deprecated_appendix = enabled;
+        self
+    }
+}
+
+impl SchemaFormatter for MarkdownFormatter {
+    fn format(&self, introspection_result: &IntrospectionResult) -> String {
+        let types = introspection_result.schema.types.iter();
+        let model = SchemaModel::build(introspection_result, types.filter(|t| !t.name.as_deref().unwrap_or_default().starts_with("__")));
+        let mut out = String::from("# Schema\n");
+        let mut deprecations = Vec::new();
+
+        if !model.objects.is_empty() {
+            out.push_str("\n## Objects\n");
+        }
+        for t in &model.objects {
+            write_heading(&mut out, &t.name, &t.description);
+            if !t.interfaces.is_empty() {
+                let interfaces: Vec<_> = t.interfaces.iter().map(|name| format!("`{}`", name)).collect();
+                out.push_str(&format!("\nImplements {}.\n", interfaces.join(", ")));
+            }
+            write_fields(&mut out, &t.name, &t.fields, &mut deprecations);
+        }
+
+        if !model.interfaces.is_empty() {
+            out.push_str("\n## Interfaces\n");
+        }
+        for t in &model.interfaces {
+            write_heading(&mut out, &t.name, &t.description);
+            write_fields(&mut out, &t.name, &t.fields, &mut deprecations);
+        }
+
+        if !model.inputs.is_empty() {
+            out.push_str("\n## Input objects\n");
+        }
+        for t in &model.inputs {
+            write_heading(&mut out, &t.name, &t.description);
+            if !t.fields.is_empty() {
+                out.push('\n');
+            }
+            for field in &t.fields {
+                out.push_str(&format!("- `{}: {}`", field.name, field.value_type));
+                if let Some(description) = &field.description {
+                    out.push_str(&format!(" — {}", description));
+                }
+                if field.is_deprecated {
+                    let reason = deprecation_reason(&field.deprecation_reason);
+                    out.push_str(&format!(" *Deprecated: {}*", reason));
+                    deprecations.push((format!("{}.{}", t.name, field.name), reason));
+                }
+                out.push('\n');
+            }
+        }
+
+        if !model.enums.is_empty() {
+            out.push_str("\n## Enums\n");
+        }
+        for t in &model.enums {
+            write_heading(&mut out, &t.name, &t.description);
+            if !t.values.is_empty() {
+                out.push('\n');
+            }
+            for value in &t.values {
+                out.push_str(&format!("- `{}`", value.name));
+                if let Some(description) = &value.description {
+                    out.push_str(&format!(" — {}", description));
+                }
+                if value.is_deprecated {
+                    let reason = deprecation_reason(&value.deprecation_reason);
+                    out.push_str(&format!(" *Deprecated: {}*", reason));
+                    deprecations.push((format!("{}.{}", t.name, value.name), reason));
+                }
+                out.push('\n');
+            }
+        }
+
+        if !model.unions.is_empty() {
+            out.push_str("\n## Unions\n");
+        }
+        for t in &model.unions {
+            write_heading(&mut out, &t.name, &t.description);
+            let members: Vec<_> = t.members.iter().map(|name| format!("`{}`", name)).collect();
+            out.push_str(&format!("\nOne of {}.\n", members.join(", ")));
+        }
+
+        let scalars: Vec<_> = model
+            .scalars
+            .iter()
+            .filter(|t| !matches!(t.name.as_str(), "Int" | "Float" | "String" | "Boolean" | "ID"))
+            .collect();
+        if !scalars.is_empty() {
+            out.push_str("\n## Scalars\n");
+        }
+        for t in scalars {
+            write_heading(&mut out, &t.name, &t.description);
+        }
+
+        if self.deprecated_appendix && !deprecations.is_empty() {
+            out.push_str("\n## Deprecated API\n\n");
+            for (path, reason) in deprecations {
+                out.push_str(&format!("- `{}` — {}\n", path, reason));
+            }
+        }
+        out
+    }
+}
+
+fn write_heading(out: &mut String, name: &str, description: &Option<String>) {
+    out.push_str(&format!("\n### {}\n", name));
+    if let Some(description) = description {
+        out.push_str(&format!("\n{}\n", description));
+    }
+}
+
+fn write_fields(out: &mut String, type_name: &str, fields: &[FieldDef], deprecations: &mut Vec<(String, String)>) {
+    if !fields.is_empty() {
+        out.push('\n');
+    }
+    for field in fields {
+        let args: Vec<_> = field.args.iter().map(|arg| format!("{}: {}", arg.name, arg.value_type)).collect();
+        let args = if args.is_empty() { String::new() } else { format!("({})", args.join(", ")) };
+        out.push_str(&format!("- `{}{}: {}`", field.name, args, field.field_type));
+        if let Some(description) = &field.description {
+            out.push_str(&format!(" — {}", description));
+        }
+        if field.is_deprecated {
+            let reason = deprecation_reason(&field.deprecation_reason);
+            out.push_str(&format!(" *Deprecated: {}*", reason));
+            deprecations.push((format!("{}.{}", type_name, field.name), reason));
+        }
+        for arg in field.args.iter().filter(|arg| arg.is_deprecated) {
+            let reason = deprecation_reason(&arg.deprecation_reason);
+            out.push_str(&format!(" *Argument `{}` deprecated: {}*", arg.name, reason));
+            deprecations.push((format!("{}.{}({}:)", type_name, field.name, arg.name), reason));
+        }
+        out.push('\n');
+    }
+}
+
+/// Returns the deprecation reason, or the default reason GraphQL uses when none is given.
+fn deprecation_reason(reason: &Option<String>) -> String {
+    reason.clone().unwrap_or_else(|| "No longer supported".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deprecated_appendix() {
+        let introspection_result: IntrospectionResult = serde_json::from_value(json!({ "__schema": { "types": [
+            { "kind": "OBJECT", "name": "User", "description": "A user", "fields": [
+                { "name": "id", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } },
+                { "name": "name", "type": { "kind": "SCALAR", "name": "String" },
+                  "isDeprecated": true, "deprecationReason": "Use `fullName` instead" },
+                { "name": "posts", "type": { "kind": "SCALAR", "name": "Int" }, "args": [
+                    { "name": "first", "type": { "kind": "SCALAR", "name": "Int" } },
+                    { "name": "limit", "type": { "kind": "SCALAR", "name": "Int" },
+                      "isDeprecated": true, "deprecationReason": "Use `first` instead" }
+                ] }
+            ] },
+            { "kind": "INPUT_OBJECT", "name": "UserInput", "inputFields": [
+                { "name": "name", "type": { "kind": "SCALAR", "name": "String" } },
+                { "name": "nick", "type": { "kind": "SCALAR", "name": "String" }, "isDeprecated": true }
+            ] },
+            { "kind": "ENUM", "name": "Role", "enumValues": [
+                { "name": "ADMIN" },
+                { "name": "ROOT", "isDeprecated": true }
+            ] }
+        ] } }))
+        .unwrap();
+
+        let markdown = MarkdownFormatter::default().with_deprecated_appendix(true).format(&introspection_result);
+        assert_eq!(
+            markdown,
+            "# Schema
+
+## Objects
+
+### User
+
+A user
+
+- `id: ID!`
+- `name: String` *Deprecated: Use `fullName` instead*
+- `posts(first: Int, limit: Int): Int` *Argument `limit` deprecated: Use `first` instead*
+
+## Input objects
+
+### UserInput
+
+- `name: String`
+- `nick: String` *Deprecated: No longer supported*
+
+## Enums
+
+### Role
+
+- `ADMIN`
+- `ROOT` *Deprecated: No longer supported*
+
+## Deprecated API
+
+- `User.name` — Use `fullName` instead
+- `User.posts(limit:)` — Use `first` instead
+- `UserInput.nick` — No longer supported
+- `Role.ROOT` — No longer supported
+"
+        );
+        assert!(!MarkdownFormatter::default().format(&introspection_result).contains("Deprecated API"));
+    }
+}
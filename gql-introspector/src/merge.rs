@@ -0,0 +1,104 @@
+use crate::{Field, GQLIntrospector, IntrospectionResult, Type};
+
+/// Merges the types of `other` into `base`, returning a description of every conflict.
+///
+/// Types are matched by name. Object, interface and input object types are combined
+/// field by field, enums by value and unions by member. Anything that cannot be
+/// combined (a different kind, or a field with a different signature) keeps the
+/// definition from `base` and is reported as a conflict.
+pub(crate) fn merge_into(base: &mut IntrospectionResult, other: IntrospectionResult) -> Vec<String> {
+    let mut conflicts = Vec::new();
+
+    for t in other.schema.types {
+        let existing = base
+            .schema
+            .types
+            .iter_mut()
+            .find(|existing| existing.name.is_some() && existing.name == t.name);
+
+        match existing {
+            Some(existing) => merge_type(existing, t, &mut conflicts),
+            None => base.schema.types.push(t),
+        }
+    }
+
+    conflicts
+}
+
+fn merge_type(existing: &mut Type, t: Type, conflicts: &mut Vec<String>) {
+    let name = existing.name.clone().unwrap_or_default();
+    if existing.kind != t.kind {
+        conflicts.push(format!(
+            "Type {} is defined as both {} and {}",
+            name,
+            existing.kind.as_deref().unwrap_or("UNKNOWN"),
+            t.kind.as_deref().unwrap_or("UNKNOWN")
+        ));
+        return;
+    }
+
+    merge_fields(&name, &mut existing.fields, t.fields, conflicts);
+    merge_fields(&name, &mut existing.input_fields, t.input_fields, conflicts);
+    merge_named(&mut existing.enum_values, t.enum_values, |value| value.name.clone());
+    merge_named(&mut existing.possible_types, t.possible_types, |t| t.name.clone());
+    merge_named(&mut existing.interfaces, t.interfaces, |t| t.name.clone());
+}
+
+fn merge_fields(type_name: &str, existing: &mut Option<Vec<Field>>, fields: Option<Vec<Field>>, conflicts: &mut Vec<String>) {
+    let Some(fields) = fields else {
+        return;
+    };
+    let existing = existing.get_or_insert_with(Vec::new);
+
+    for field in fields {
+        match existing.iter().find(|e| e.name == field.name) {
+            Some(e) if signature(e) != signature(&field) => conflicts.push(format!(
+                "Field {}.{} differs: {} vs {}",
+                type_name,
+                field.name.as_deref().unwrap_or_default(),
+                signature(e),
+                signature(&field)
+            )),
+            Some(_) => {}
+            None => existing.push(field),
+        }
+    }
+}
+
+fn merge_named<T, F>(existing: &mut Option<Vec<T>>, items: Option<Vec<T>>, name: F)
+where
+    F: Fn(&T) -> Option<String>,
+{
+    let Some(items) = items else {
+        return;
+    };
+    let existing = existing.get_or_insert_with(Vec::new);
+
+    for item in items {
+        if !existing.iter().any(|e| name(e) == name(&item)) {
+            existing.push(item);
+        }
+    }
+}
+
+pub(crate) fn signature(field: &Field) -> String {
+    let args = field
+        .args
+        .iter()
+        .flatten()
+        .map(|arg| {
+            format!(
+                "{}: {}",
+                arg.name.as_deref().unwrap_or_default(),
+                arg.field_type.as_ref().map(GQLIntrospector::format_type).unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+    let field_type = field.field_type.as_ref().map(GQLIntrospector::format_type).unwrap_or_default();
+
+    if args.is_empty() {
+        field_type
+    } else {
+        format!("({}): {}", args.join(", "), field_type)
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+
+use crate::{Field, IntrospectionResult, Type, Value};
+
+/// A reference to a type, as used by fields, arguments and input fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+impl TypeRef {
+    /// Builds a `TypeRef` from an introspected type reference, or `None` if it is incomplete.
+    pub fn from_type(t: &Type) -> Option<Self> {
+        match t.kind.as_deref() {
+            Some("LIST") => Some(TypeRef::List(Box::new(Self::from_type(t.of_type.as_ref()?)?))),
+            Some("NON_NULL") => Some(TypeRef::NonNull(Box::new(Self::from_type(t.of_type.as_ref()?)?))),
+            _ => Some(TypeRef::Named(t.name.clone()?)),
+        }
+    }
+
+    /// Returns the name of the named type at the core of the reference.
+    pub fn named_type(&self) -> &str {
+        match self {
+            TypeRef::Named(name) => name,
+            TypeRef::List(of_type) | TypeRef::NonNull(of_type) => of_type.named_type(),
+        }
+    }
+}
+
+impl fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeRef::Named(name) => write!(f, "{}", name),
+            TypeRef::List(of_type) => write!(f, "[{}]", of_type),
+            TypeRef::NonNull(of_type) => write!(f, "{}!", of_type),
+        }
+    }
+}
+
+/// An argument or input object field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub value_type: TypeRef,
+    pub default_value: Option<String>,
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+/// A field of an object or interface type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDef {
+    pub name: String,
+    pub description: Option<String>,
+    pub args: Vec<InputValue>,
+    pub field_type: TypeRef,
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+/// An object type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectType {
+    pub name: String,
+    pub description: Option<String>,
+    pub interfaces: Vec<String>,
+    pub fields: Vec<FieldDef>,
+}
+
+/// An interface type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceType {
+    pub name: String,
+    pub description: Option<String>,
+    pub fields: Vec<FieldDef>,
+}
+
+/// An input object type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputObjectType {
+    pub name: String,
+    pub description: Option<String>,
+    pub fields: Vec<InputValue>,
+}
+
+/// A value of an enum type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub is_deprecated: bool,
+    pub deprecation_reason: Option<String>,
+}
+
+/// An enum type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumType {
+    pub name: String,
+    pub description: Option<String>,
+    pub values: Vec<EnumValue>,
+}
+
+/// A scalar type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScalarType {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// A union type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnionType {
+    pub name: String,
+    pub description: Option<String>,
+    pub members: Vec<String>,
+}
+
+/// A typed, in-memory representation of a schema, built from an `IntrospectionResult`.
+///
+/// Unlike the introspection structs, everything a valid schema always has is
+/// required here; incomplete entries (e.g. a field without a type) are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaModel {
+    pub query_type: Option<String>,
+    pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+    pub objects: Vec<ObjectType>,
+    pub interfaces: Vec<InterfaceType>,
+    pub inputs: Vec<InputObjectType>,
+    pub enums: Vec<EnumType>,
+    pub scalars: Vec<ScalarType>,
+    pub unions: Vec<UnionType>,
+}
+
+impl SchemaModel {
+    /// Builds the model from the root types of the introspection result and the given types.
+    pub(crate) fn build<'a>(introspection_result: &IntrospectionResult, types: impl Iterator<Item = &'a Type>) -> Self {
+        let schema = &introspection_result.schema;
+        let root_name = |t: &Option<Type>| t.as_ref().and_then(|t| t.name.clone());
+        let mut model = SchemaModel {
+            query_type: root_name(&schema.query_type),
+            mutation_type: root_name(&schema.mutation_type),
+            subscription_type: root_name(&schema.subscription_type),
+            ..SchemaModel::default()
+        };
+
+        for t in types {
+            let (Some(name), description) = (t.name.clone(), t.description.clone()) else {
+                continue;
+            };
+            match t.kind.as_deref() {
+                Some("OBJECT") => model.objects.push(ObjectType {
+                    name,
+                    description,
+                    interfaces: names(&t.interfaces),
+                    fields: fields(&t.fields),
+                }),
+                Some("INTERFACE") => model.interfaces.push(InterfaceType { name, description, fields: fields(&t.fields) }),
+                Some("INPUT_OBJECT") => model.inputs.push(InputObjectType { name, description, fields: input_values(&t.input_fields) }),
+                Some("ENUM") => model.enums.push(EnumType { name, description, values: enum_values(&t.enum_values) }),
+                Some("SCALAR") => model.scalars.push(ScalarType { name, description }),
+                Some("UNION") => model.unions.push(UnionType { name, description, members: names(&t.possible_types) }),
+                _ => {}
+            }
+        }
+
+        model
+    }
+}
+
+fn names(types: &Option<Vec<Type>>) -> Vec<String> {
+    types.iter().flatten().filter_map(|t| t.name.clone()).collect()
+}
+
+fn input_values(fields: &Option<Vec<Field>>) -> Vec<InputValue> {
+    fields
+        .iter()
+        .flatten()
+        .filter_map(|field| {
+            Some(InputValue {
+                name: field.name.clone()?,
+                description: field.description.clone(),
+                value_type: TypeRef::from_type(field.field_type.as_ref()?)?,
+                default_value: field.default_value.clone(),
+                is_deprecated: field.is_deprecated.unwrap_or(false),
+                deprecation_reason: field.deprecation_reason.clone(),
+            })
+        })
+        .collect()
+}
+
+fn fields(fields: &Option<Vec<Field>>) -> Vec<FieldDef> {
+    fields
+        .iter()
+        .flatten()
+        .filter_map(|field| {
+            Some(FieldDef {
+                name: field.name.clone()?,
+                description: field.description.clone(),
+                args: input_values(&field.args),
+                field_type: TypeRef::from_type(field.field_type.as_ref()?)?,
+                is_deprecated: field.is_deprecated.unwrap_or(false),
+                deprecation_reason: field.deprecation_reason.clone(),
+            })
+        })
+        .collect()
+}
+
+fn enum_values(values: &Option<Vec<Value>>) -> Vec<EnumValue> {
+    values
+        .iter()
+        .flatten()
+        .filter_map(|value| {
+            Some(EnumValue {
+                name: value.name.clone()?,
+                description: value.description.clone(),
+                is_deprecated: value.is_deprecated.unwrap_or(false),
+                deprecation_reason: value.deprecation_reason.clone(),
+            })
+        })
+        .collect()
+}
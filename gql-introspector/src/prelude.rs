@@ -0,0 +1,12 @@
+//! Commonly used types from both `gql_introspector` and `gqlclient`, re-exported for convenience.
+//!
+//! ```no_run
+//! use gql_introspector::prelude::*;
+//!
+//! let introspector = GQLIntrospector::new();
+//! let client = GQLClient::new("https://api.github.com/graphql");
+//! ```
+
+pub use gqlclient::prelude::*;
+
+pub use crate::{Capabilities, GQLIntrospector, IntrospectionQueryOptions, RootKind, SchemaDiff, SchemaFormatter, Verbosity};
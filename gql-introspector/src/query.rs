@@ -0,0 +1,139 @@
+/// Options controlling which parts of the schema the introspection query fetches.
+///
+/// Leaving out parts that are not rendered keeps responses small for large schemas.
+///
+/// ```
+/// use gql_introspector::IntrospectionQueryOptions;
+///
+/// let query = IntrospectionQueryOptions {
+///     include_descriptions: false,
+///     ..Default::default()
+/// }
+/// .to_query();
+/// assert!(!query.contains("description"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntrospectionQueryOptions {
+    /// Fetches the descriptions of types, fields, arguments and enum values.
+    pub include_descriptions: bool,
+    /// Fetches deprecated fields, arguments, input fields and enum values along with their deprecation status.
+    pub include_deprecated: bool,
+    /// Fetches the arguments of fields.
+    pub include_args: bool,
+    /// How many `ofType` levels are fetched for type references, enough for `[User!]!` at 3.
+    pub of_type_depth: usize,
+    /// Fetches the description of the schema itself, along with `include_descriptions`.
+    ///
+    /// Off by default, since servers implementing specifications older than October 2021
+    /// reject `description` on `__schema`.
+    pub include_schema_description: bool,
+    /// Fetches the directives applied to types and fields, such as federation's `@key`.
+    ///
+    /// Off by default, since it relies on the `appliedDirectives` extension that only
+    /// some servers support.
+    #[cfg(feature = "federation")]
+    pub include_applied_directives: bool,
+}
+
+impl Default for IntrospectionQueryOptions {
+    fn default() -> Self {
+        Self {
+            include_descriptions: true,
+            include_deprecated: true,
+            include_args: true,
+            of_type_depth: 3,
+            include_schema_description: false,
+            #[cfg(feature = "federation")]
+            include_applied_directives: false,
+        }
+    }
+}
+
+impl IntrospectionQueryOptions {
+    /// Generates the introspection query for these options.
+    pub fn to_query(&self) -> String {
+        let description = if self.include_descriptions { " description" } else { "" };
+        let include_deprecated = if self.include_deprecated { "(includeDeprecated: true)" } else { "" };
+        let deprecation = if self.include_deprecated { " isDeprecated deprecationReason" } else { "" };
+        let schema_description = if self.include_descriptions && self.include_schema_description { "description " } else { "" };
+        let type_ref = self.type_ref(self.of_type_depth);
+        #[cfg(feature = "federation")]
+        let directives = if self.include_applied_directives { " appliedDirectives { name args { name value } }" } else { "" };
+        #[cfg(not(feature = "federation"))]
+        let directives = "";
+
+        let mut field = format!("name{}", description);
+        if self.include_args {
+            field.push_str(&format!(" args{} {{ name{} type {{ {} }} defaultValue{} }}", include_deprecated, description, type_ref, deprecation));
+        }
+        field.push_str(&format!(" type {{ {} }}{}{}", type_ref, deprecation, directives));
+
+        let lines = [
+            "query {".to_string(),
+            "    __schema {".to_string(),
+            format!("        {}queryType {{ name }}", schema_description),
+            "        mutationType { name }".to_string(),
+            "        subscriptionType { name }".to_string(),
+            "        types {".to_string(),
+            format!("            kind name{}{}", description, directives),
+            format!("            fields{} {{ {} }}", include_deprecated, field),
+            format!("            inputFields{} {{ name{} type {{ {} }} defaultValue{}{} }}", include_deprecated, description, type_ref, deprecation, directives),
+            format!("            interfaces {{ {} }}", type_ref),
+            format!("            enumValues{} {{ name{}{} }}", include_deprecated, description, deprecation),
+            format!("            possibleTypes {{ {} }}", type_ref),
+            "        }".to_string(),
+            "    }".to_string(),
+            "}".to_string(),
+        ];
+        lines.join("\n")
+    }
+
+    fn type_ref(&self, depth: usize) -> String {
+        match depth {
+            0 => "kind name".to_string(),
+            _ => format!("kind name ofType {{ {} }}", self.type_ref(depth - 1)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_query() {
+        let query = IntrospectionQueryOptions::default().to_query();
+        assert!(query.contains("fields(includeDeprecated: true) { name description args(includeDeprecated: true) { name description type"));
+        assert!(query.contains("defaultValue isDeprecated deprecationReason }"));
+        assert!(query.contains("            inputFields(includeDeprecated: true) { name description type"));
+        assert!(query.contains("type { kind name ofType { kind name ofType { kind name ofType { kind name } } } } isDeprecated deprecationReason"));
+
+        let query = IntrospectionQueryOptions {
+            include_descriptions: false,
+            include_deprecated: false,
+            include_args: false,
+            of_type_depth: 1,
+            include_schema_description: true,
+            #[cfg(feature = "federation")]
+            include_applied_directives: false,
+        }
+        .to_query();
+        assert!(!query.contains("description"));
+        assert!(!query.contains("eprecat"));
+        assert!(!query.contains("args"));
+        assert!(query.contains("            fields { name type { kind name ofType { kind name } } }"));
+        assert!(!query.contains("appliedDirectives"));
+    }
+
+    #[cfg(feature = "federation")]
+    #[test]
+    fn test_to_query_with_applied_directives() {
+        let query = IntrospectionQueryOptions {
+            include_applied_directives: true,
+            ..Default::default()
+        }
+        .to_query();
+        assert!(query.contains("            kind name description appliedDirectives { name args { name value } }"));
+        assert!(query.contains("isDeprecated deprecationReason appliedDirectives { name args { name value } } }"));
+    }
+}
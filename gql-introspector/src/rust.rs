@@ -0,0 +1,355 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{FieldDef, InputValue, IntrospectionResult, SchemaFormatter, SchemaModel, TypeRef};
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum", "extern",
+    "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override",
+    "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Generates serde-compatible Rust types from the schema.
+///
+/// Object and input object types become structs, enums become enums, and custom
+/// scalars become type aliases. Interfaces and unions are aliased to
+/// `serde_json::Value`.
+///
+/// ```
+/// use gql_introspector::RustFormatter;
+///
+/// let formatter = RustFormatter::default()
+///     .map_scalar("DateTime", "chrono::DateTime<chrono::Utc>")
+///     .map_scalar("JSON", "serde_json::Value");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RustFormatter {
+    /// The Rust type used for each custom scalar, e.g. `DateTime -> chrono::DateTime<chrono::Utc>`.
+    ///
+    /// Unmapped custom scalars default to `String`, marked by a comment in the generated code.
+    pub scalar_map: HashMap<String, String>,
+}
+
+impl RustFormatter {
+    /// Maps a custom scalar to a Rust type, given by its full path.
+    ///
+    /// # Arguments
+    ///
+    /// * `scalar` - The name of the GraphQL scalar.
+    /// * `rust_type` - The Rust type, e.g. `chrono::DateTime<chrono::Utc>`.
+    pub fn map_scalar(mut self, scalar: &str, rust_type: &str) -> Self {
+        self.scalar_map.insert(scalar.to_string(), rust_type.to_string());
+        self
+    }
+
+    fn rust_type(&self, t: &TypeRef, boxed: &HashSet<&str>) -> String {
+        match t {
+            TypeRef::NonNull(of_type) => self.non_null_type(of_type, boxed),
+            t => format!("Option<{}>", self.non_null_type(t, boxed)),
+        }
+    }
+
+    fn non_null_type(&self, t: &TypeRef, boxed: &HashSet<&str>) -> String {
+        match t {
+            TypeRef::NonNull(of_type) => self.non_null_type(of_type, boxed),
+            // Vec already adds indirection, so only direct references need a Box.
+            TypeRef::List(of_type) => format!("Vec<{}>", self.rust_type(of_type, &HashSet::new())),
+            TypeRef::Named(name) => match name.as_str() {
+                "Int" => "i32".to_string(),
+                "Float" => "f64".to_string(),
+                "String" | "ID" => "String".to_string(),
+                "Boolean" => "bool".to_string(),
+                name if boxed.contains(name) => format!("Box<{}>", name),
+                name => name.to_string(),
+            },
+        }
+    }
+
+    fn write_struct<'a, I>(&self, out: &mut String, name: &str, description: &Option<String>, fields: I, boxed: &HashSet<&str>)
+    where
+        I: Iterator<Item = (&'a str, &'a Option<String>, &'a TypeRef)>,
+    {
+        write_doc(out, description, "");
+        out.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        out.push_str(&format!("pub struct {} {{\n", name));
+        for (name, description, field_type) in fields {
+            write_doc(out, description, "    ");
+            let rust_name = field_name(name);
+            if rust_name.trim_start_matches("r#") != name {
+                out.push_str(&format!("    #[serde(rename = \"{}\")]\n", name));
+            }
+            out.push_str(&format!("    pub {}: {},\n", rust_name, self.rust_type(field_type, boxed)));
+        }
+        out.push_str("}\n\n");
+    }
+}
+
+impl SchemaFormatter for RustFormatter {
+    fn format(&self, introspection_result: &IntrospectionResult) -> String {
+        let types = introspection_result.schema.types.iter();
+        let model = SchemaModel::build(introspection_result, types.filter(|t| !t.name.as_deref().unwrap_or_default().starts_with("__")));
+        let boxed = boxed_references(&model);
+
+        let mut body = String::new();
+        let mut crates = BTreeSet::new();
+
+        for scalar in &model.scalars {
+            if matches!(scalar.name.as_str(), "Int" | "Float" | "String" | "Boolean" | "ID") {
+                continue;
+            }
+            let rust_type = match self.scalar_map.get(&scalar.name) {
+                Some(rust_type) => rust_type.clone(),
+                None => {
+                    body.push_str(&format!("// Unmapped custom scalar {}, defaulting to String\n", scalar.name));
+                    "String".to_string()
+                }
+            };
+            if let Some((krate, _)) = rust_type.split_once("::") {
+                crates.insert(krate.to_string());
+            }
+            write_doc(&mut body, &scalar.description, "");
+            body.push_str(&format!("pub type {} = {};\n\n", scalar.name, rust_type));
+        }
+
+        let abstract_types = model
+            .interfaces
+            .iter()
+            .map(|t| (&t.name, &t.description))
+            .chain(model.unions.iter().map(|t| (&t.name, &t.description)));
+        for (name, description) in abstract_types {
+            crates.insert("serde_json".to_string());
+            write_doc(&mut body, description, "");
+            body.push_str(&format!("pub type {} = serde_json::Value;\n\n", name));
+        }
+
+        for t in &model.enums {
+            write_doc(&mut body, &t.description, "");
+            body.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\n");
+            body.push_str(&format!("pub enum {} {{\n", t.name));
+            let mut variants = HashSet::new();
+            for value in &t.values {
+                write_doc(&mut body, &value.description, "    ");
+                // Values such as `FOO_BAR` and `foo_bar` map to the same name; number the later ones.
+                let base = variant_name(&value.name);
+                let mut variant = base.clone();
+                for n in 2.. {
+                    if variants.insert(variant.clone()) {
+                        break;
+                    }
+                    variant = format!("{}{}", base, n);
+                }
+                if variant != value.name {
+                    body.push_str(&format!("    #[serde(rename = \"{}\")]\n", value.name));
+                }
+                body.push_str(&format!("    {},\n", variant));
+            }
+            body.push_str("}\n\n");
+        }
+
+        for t in &model.objects {
+            self.write_struct(&mut body, &t.name, &t.description, t.fields.iter().map(field_def), &boxed[t.name.as_str()]);
+        }
+        for t in &model.inputs {
+            self.write_struct(&mut body, &t.name, &t.description, t.fields.iter().map(input_value), &boxed[t.name.as_str()]);
+        }
+
+        let mut out = String::from("// Generated by gql-rs — do not edit\n");
+        if !crates.is_empty() {
+            out.push_str(&format!("// Requires the crates: {}\n", crates.into_iter().collect::<Vec<_>>().join(", ")));
+        }
+        out.push_str("\nuse serde::{Deserialize, Serialize};\n\n");
+        out.push_str(&body);
+        out
+    }
+}
+
+fn field_def(field: &FieldDef) -> (&str, &Option<String>, &TypeRef) {
+    (&field.name, &field.description, &field.field_type)
+}
+
+fn input_value(field: &InputValue) -> (&str, &Option<String>, &TypeRef) {
+    (&field.name, &field.description, &field.value_type)
+}
+
+fn write_doc(out: &mut String, description: &Option<String>, indent: &str) {
+    for line in description.iter().flat_map(|description| description.lines()) {
+        out.push_str(&format!("{}/// {}\n", indent, line).replace("/// \n", "///\n"));
+    }
+}
+
+/// Converts a GraphQL field name such as `firstName` or `URLPath` to a Rust field name.
+fn field_name(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lower) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    match snake.as_str() {
+        "self" | "super" | "crate" => format!("{}_", snake),
+        keyword if KEYWORDS.contains(&keyword) => format!("r#{}", snake),
+        _ => snake,
+    }
+}
+
+/// Returns, for each struct, the types its fields must box: those referenced directly
+/// rather than through a `Vec` that lead back to the struct, which would otherwise have
+/// an infinite size.
+fn boxed_references(model: &SchemaModel) -> HashMap<&str, HashSet<&str>> {
+    let direct: HashMap<&str, Vec<&str>> = model
+        .objects
+        .iter()
+        .map(|t| (t.name.as_str(), t.fields.iter().filter_map(|field| direct_reference(&field.field_type)).collect()))
+        .chain(model.inputs.iter().map(|t| (t.name.as_str(), t.fields.iter().filter_map(|field| direct_reference(&field.value_type)).collect())))
+        .collect();
+    let reaches = |from: &str, to: &str| {
+        let mut stack = vec![from];
+        let mut seen = HashSet::new();
+        while let Some(name) = stack.pop() {
+            if name == to {
+                return true;
+            }
+            stack.extend(direct.get(name).into_iter().flatten().filter(|next| seen.insert(**next)));
+        }
+        false
+    };
+    direct
+        .iter()
+        .map(|(&owner, targets)| (owner, targets.iter().copied().filter(|target| reaches(target, owner)).collect()))
+        .collect()
+}
+
+/// Returns the named type of a reference that is not wrapped in a list.
+fn direct_reference(t: &TypeRef) -> Option<&str> {
+    match t {
+        TypeRef::NonNull(of_type) => direct_reference(of_type),
+        TypeRef::List(_) => None,
+        TypeRef::Named(name) => Some(name),
+    }
+}
+
+/// Converts an enum value such as `IN_PROGRESS` to a Rust variant name such as `InProgress`.
+///
+/// Names that would start with a digit get a leading `_`, and `Self` becomes `Self_`.
+fn variant_name(name: &str) -> String {
+    let variant: String = name
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            first.into_iter().chain(chars.flat_map(char::to_lowercase)).collect::<String>()
+        })
+        .collect();
+    match variant.chars().next() {
+        None => format!("_{}", name),
+        Some(c) if c.is_ascii_digit() => format!("_{}", variant),
+        _ if variant == "Self" => "Self_".to_string(),
+        _ => variant,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_format() {
+        let introspection_result: IntrospectionResult = serde_json::from_value(json!({ "__schema": { "types": [
+            { "kind": "SCALAR", "name": "DateTime" },
+            { "kind": "SCALAR", "name": "Cursor" },
+            { "kind": "ENUM", "name": "Status", "enumValues": [{ "name": "IN_PROGRESS" }] },
+            { "kind": "OBJECT", "name": "User", "description": "A user", "fields": [
+                { "name": "id", "type": { "kind": "NON_NULL", "ofType": { "kind": "SCALAR", "name": "ID" } } },
+                { "name": "createdAt", "type": { "kind": "SCALAR", "name": "DateTime" } },
+                { "name": "type", "type": { "kind": "ENUM", "name": "Status" } },
+                { "name": "manager", "type": { "kind": "OBJECT", "name": "User" } },
+                { "name": "friends", "type": { "kind": "LIST", "ofType": { "kind": "OBJECT", "name": "User" } } },
+                { "name": "posts", "type": { "kind": "LIST", "ofType": { "kind": "OBJECT", "name": "Post" } } }
+            ] },
+            { "kind": "OBJECT", "name": "Post", "fields": [
+                { "name": "author", "type": { "kind": "NON_NULL", "ofType": { "kind": "OBJECT", "name": "User" } } }
+            ] }
+        ] } }))
+        .unwrap();
+
+        let rust = RustFormatter::default().map_scalar("DateTime", "chrono::DateTime<chrono::Utc>").format(&introspection_result);
+        assert_eq!(
+            rust,
+            "// Generated by gql-rs — do not edit
+// Requires the crates: chrono
+
+use serde::{Deserialize, Serialize};
+
+pub type DateTime = chrono::DateTime<chrono::Utc>;
+
+// Unmapped custom scalar Cursor, defaulting to String
+pub type Cursor = String;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    #[serde(rename = \"IN_PROGRESS\")]
+    InProgress,
+}
+
+/// A user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    #[serde(rename = \"createdAt\")]
+    pub created_at: Option<DateTime>,
+    pub r#type: Option<Status>,
+    pub manager: Option<Box<User>>,
+    pub friends: Option<Vec<Option<User>>>,
+    pub posts: Option<Vec<Option<Post>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Post {
+    pub author: User,
+}
+
+"
+        );
+        assert_eq!(field_name("URLPath"), "url_path");
+        assert_eq!(field_name("self"), "self_");
+    }
+
+    #[test]
+    fn test_enum_variant_names() {
+        let introspection_result: IntrospectionResult = serde_json::from_value(json!({ "__schema": { "types": [
+            { "kind": "ENUM", "name": "Value", "enumValues": [
+                { "name": "SELF" }, { "name": "_1080P" }, { "name": "FOO_BAR" }, { "name": "foo_bar" }, { "name": "_" }
+            ] },
+            { "kind": "OBJECT", "name": "A", "fields": [{ "name": "b", "type": { "kind": "OBJECT", "name": "B" } }] },
+            { "kind": "OBJECT", "name": "B", "fields": [{ "name": "a", "type": { "kind": "OBJECT", "name": "A" } }] }
+        ] } }))
+        .unwrap();
+
+        let rust = RustFormatter::default().format(&introspection_result);
+        assert!(rust.contains(
+            "pub enum Value {
+    #[serde(rename = \"SELF\")]
+    Self_,
+    #[serde(rename = \"_1080P\")]
+    _1080p,
+    #[serde(rename = \"FOO_BAR\")]
+    FooBar,
+    #[serde(rename = \"foo_bar\")]
+    FooBar2,
+    #[serde(rename = \"_\")]
+    __,
+}"
+        ));
+        // Types referencing each other directly are boxed on both sides.
+        assert!(rust.contains("pub b: Option<Box<B>>,") && rust.contains("pub a: Option<Box<A>>,"));
+    }
+}
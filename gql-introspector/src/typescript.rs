@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+
+use gqlclient::document::{self, FragmentDefinition, OperationType, Selection};
+
+use crate::{IntrospectionResult, RootKind, Type};
+
+/// A field of the result type, merged across every selection of its response key.
+struct Field<'s> {
+    key: &'s str,
+    name: &'s str,
+    parent: &'s Type,
+    /// Whether the field is only selected through fragments narrower than its parent.
+    optional: bool,
+    selections: Vec<&'s Selection>,
+}
+
+/// Resolves the selections of a document against a schema.
+struct Resolver<'a> {
+    types: HashMap<&'a str, &'a Type>,
+    fragments: HashMap<String, FragmentDefinition>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Renders the object type produced by `selections` on `parent`.
+    fn object<'s>(&'s self, parent: &'s Type, selections: Vec<&'s Selection>, indent: &str) -> Result<String, String> {
+        let mut fields = Vec::new();
+        self.collect(parent, selections, false, &mut fields, &mut HashSet::new())?;
+
+        let mut out = String::from("{\n");
+        for field in fields {
+            let ts_type = self.field_type(&field, indent)?;
+            out.push_str(&format!("{}  {}{}: {};\n", indent, field.key, if field.optional { "?" } else { "" }, ts_type));
+        }
+        out.push_str(indent);
+        out.push('}');
+        Ok(out)
+    }
+
+    /// Collects the fields selected on `parent`. Fields selected through a fragment on
+    /// another type than `parent` only exist for some of its possible types, so they are
+    /// optional unless the same key is also selected unconditionally. The sub-selections
+    /// of every selection of a key are merged.
+    fn collect<'s>(
+        &'s self,
+        parent: &'s Type,
+        selections: Vec<&'s Selection>,
+        optional: bool,
+        fields: &mut Vec<Field<'s>>,
+        spreading: &mut HashSet<String>,
+    ) -> Result<(), String> {
+        for selection in selections {
+            match selection {
+                Selection::Field { alias, name, selection_set } => {
+                    let key = alias.as_deref().unwrap_or(name);
+                    match fields.iter_mut().find(|field| field.key == key) {
+                        Some(field) => {
+                            field.optional &= optional;
+                            field.selections.extend(selection_set);
+                        }
+                        None => fields.push(Field { key, name, parent, optional, selections: selection_set.iter().collect() }),
+                    }
+                }
+                Selection::InlineFragment { type_condition, selection_set } => {
+                    let target = self.fragment_target(parent, type_condition.as_deref())?;
+                    self.collect(target, selection_set.iter().collect(), optional || !std::ptr::eq(target, parent), fields, spreading)?;
+                }
+                Selection::FragmentSpread(name) => {
+                    let fragment = self.fragments.get(name).ok_or_else(|| format!("Unknown fragment {}", name))?;
+                    if !spreading.insert(name.clone()) {
+                        return Err(format!("Fragment {} spreads itself", name));
+                    }
+                    let target = self.fragment_target(parent, Some(&fragment.type_condition))?;
+                    let selections = fragment.selection_set.iter().collect();
+                    self.collect(target, selections, optional || !std::ptr::eq(target, parent), fields, spreading)?;
+                    spreading.remove(name);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Renders the TypeScript type of a collected field.
+    fn field_type(&self, field: &Field, indent: &str) -> Result<String, String> {
+        let parent_name = field.parent.name.as_deref().unwrap_or_default();
+        if field.name == "__typename" {
+            return Ok(match field.parent.kind.as_deref() {
+                Some("OBJECT") => format!("\"{}\"", parent_name),
+                _ => "string".to_string(),
+            });
+        }
+        let schema_field = field
+            .parent
+            .fields
+            .iter()
+            .flatten()
+            .find(|schema_field| schema_field.name.as_deref() == Some(field.name))
+            .ok_or_else(|| format!("Unknown field {} on type {}", field.name, parent_name))?;
+        let field_type = schema_field.field_type.as_ref().ok_or_else(|| format!("Field {}.{} has no type", parent_name, field.name))?;
+        self.ts_type(field_type, &field.selections, &format!("{}  ", indent), &format!("{}.{}", parent_name, field.name))
+    }
+
+    /// Returns the type the selections of a fragment resolve against: `parent` itself, or
+    /// the narrower type named by the type condition.
+    fn fragment_target<'t>(&'t self, parent: &'t Type, type_condition: Option<&str>) -> Result<&'t Type, String> {
+        match type_condition {
+            Some(condition) if Some(condition) != parent.name.as_deref() => {
+                self.types.get(condition).copied().ok_or_else(|| format!("Fragment on undefined type {}", condition))
+            }
+            _ => Ok(parent),
+        }
+    }
+
+    /// Renders the TypeScript type of a field of type `t`.
+    fn ts_type(&self, t: &Type, selections: &[&Selection], indent: &str, path: &str) -> Result<String, String> {
+        let non_null = match t.kind.as_deref() {
+            Some("NON_NULL") => {
+                let of_type = t.of_type.as_ref().ok_or_else(|| format!("Field {} has an incomplete type", path))?;
+                return self.non_null_ts_type(of_type, selections, indent, path);
+            }
+            _ => self.non_null_ts_type(t, selections, indent, path)?,
+        };
+        Ok(format!("{} | null", non_null))
+    }
+
+    fn non_null_ts_type(&self, t: &Type, selections: &[&Selection], indent: &str, path: &str) -> Result<String, String> {
+        if t.kind.as_deref() == Some("LIST") {
+            let of_type = t.of_type.as_ref().ok_or_else(|| format!("Field {} has an incomplete type", path))?;
+            return Ok(format!("Array<{}>", self.ts_type(of_type, selections, indent, path)?));
+        }
+
+        let name = t.name.as_deref().unwrap_or_default();
+        // Built-in scalars are not always listed in the introspection result.
+        let named = match self.types.get(name) {
+            Some(named) => *named,
+            None if t.kind.as_deref() == Some("SCALAR") => t,
+            None => return Err(format!("Field {} references undefined type {}", path, name)),
+        };
+        match named.kind.as_deref() {
+            Some("OBJECT" | "INTERFACE" | "UNION") if selections.is_empty() => {
+                Err(format!("Field {} of type {} needs a selection set", path, name))
+            }
+            Some("OBJECT" | "INTERFACE" | "UNION") => self.object(named, selections.to_vec(), indent),
+            Some("ENUM") => {
+                let values: Vec<_> = named.enum_values.iter().flatten().filter_map(|value| value.name.as_ref()).map(|value| format!("\"{}\"", value)).collect();
+                Ok(if values.is_empty() { "never".to_string() } else { values.join(" | ") })
+            }
+            _ => Ok(match name {
+                "Int" | "Float" => "number",
+                "String" | "ID" => "string",
+                "Boolean" => "boolean",
+                _ => "unknown",
+            }
+            .to_string()),
+        }
+    }
+}
+
+/// Generates the TypeScript result type of each operation in `query`.
+pub(crate) fn operation_types(introspection_result: &IntrospectionResult, query: &str) -> Result<String, String> {
+    let document = document::parse(query);
+    if document.operations.is_empty() {
+        return Err("The document has no operations".to_string());
+    }
+
+    let schema = &introspection_result.schema;
+    let types = schema.types.iter().filter_map(|t| Some((t.name.as_deref()?, t))).collect();
+    let resolver = Resolver { types, fragments: document.fragments };
+    let mut out = String::new();
+    for operation in &document.operations {
+        let kind = match operation.operation_type {
+            OperationType::Query => RootKind::Query,
+            OperationType::Mutation => RootKind::Mutation,
+            OperationType::Subscription => RootKind::Subscription,
+        };
+        let root_name = kind.type_name(schema);
+        let root = resolver.types.get(root_name.as_str()).ok_or_else(|| format!("The schema has no {} type", root_name))?;
+        let type_name = format!("{}Result", operation.name.as_deref().unwrap_or(&root_name));
+        out.push_str(&format!("export type {} = {};\n\n", type_name, resolver.object(root, operation.selection_set.iter().collect(), "")?));
+    }
+    out.pop();
+    Ok(out)
+}
@@ -0,0 +1,22 @@
+extern crate gqlclient;
+
+use std::time::Duration;
+
+use gqlclient::{GQLClient, QueryBuilder, RetryPolicy};
+
+fn main() {
+    let client = GQLClient::new("http://localhost:4000/graphql/stream");
+    let query_builder = QueryBuilder::new("subscription { messageAdded { id text } }");
+
+    let subscription = client
+        .subscribe::<serde_json::Value>(&query_builder)
+        .expect("Invalid subscription")
+        .with_reconnect(RetryPolicy::new(5, Duration::from_secs(1)));
+
+    for message in subscription {
+        match message {
+            Ok(data) => println!("Message: {}", data),
+            Err(e) => eprintln!("Subscription error: {}", e),
+        }
+    }
+}
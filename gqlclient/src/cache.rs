@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Raw response bodies of successful queries, kept for `ttl` in memory and, optionally,
+/// as one file per key in a directory.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    ttl: Duration,
+    dir: Option<PathBuf>,
+    entries: Mutex<HashMap<String, (Instant, String)>>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(ttl: Duration, dir: Option<PathBuf>) -> Self {
+        Self { ttl, dir, entries: Mutex::new(HashMap::new()) }
+    }
+
+    /// Returns the cached body for `key` if it is younger than the TTL.
+    pub(crate) fn get(&self, key: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match entries.get(key) {
+            Some((stored_at, raw_body)) if stored_at.elapsed() < self.ttl => return Some(raw_body.clone()),
+            Some(_) => {
+                entries.remove(key);
+            }
+            None => {}
+        }
+
+        let path = self.dir.as_ref()?.join(format!("{}.json", key));
+        let age = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok()?.elapsed().unwrap_or_default();
+        if age >= self.ttl {
+            return None;
+        }
+        let raw_body = fs::read_to_string(&path).ok()?;
+        // Keep the remaining lifetime of the entry rather than restarting its TTL.
+        entries.insert(key.to_string(), (Instant::now().checked_sub(age).unwrap_or_else(Instant::now), raw_body.clone()));
+        Some(raw_body)
+    }
+
+    /// Stores the body for `key`. Failing to write the disk copy is not an error, since
+    /// the cache only saves network calls.
+    pub(crate) fn insert(&self, key: &str, raw_body: &str) {
+        if let Some(dir) = &self.dir {
+            let _ = fs::create_dir_all(dir).and_then(|()| fs::write(dir.join(format!("{}.json", key)), raw_body));
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+        entries.insert(key.to_string(), (Instant::now(), raw_body.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_disk_cache_outlives_memory() {
+        let dir = std::env::temp_dir().join(format!("gqlclient-cache-{}-{}", std::process::id(), UNIX_EPOCH.elapsed().unwrap().as_nanos()));
+        ResponseCache::new(Duration::from_secs(60), Some(dir.clone())).insert("abc", r#"{"data":{}}"#);
+
+        let cache = ResponseCache::new(Duration::from_secs(60), Some(dir.clone()));
+        assert_eq!(cache.get("abc").as_deref(), Some(r#"{"data":{}}"#));
+        assert_eq!(cache.get("missing"), None);
+        assert_eq!(ResponseCache::new(Duration::ZERO, Some(dir.clone())).get("abc"), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
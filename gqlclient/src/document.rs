@@ -0,0 +1,738 @@
+//! Minimal, lexer-level helpers for working with GraphQL documents.
+//!
+//! These helpers do not validate documents; they only understand enough of the
+//! GraphQL grammar to split and inspect definitions safely (strings, block strings
+//! and comments are never mistaken for punctuation). `parse` exposes the selections
+//! of each operation and fragment, for tools that resolve them against a schema.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    Punctuator,
+    Name,
+    Number,
+    String,
+    BlockString,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Splits a GraphQL document into tokens, skipping whitespace, commas and comments.
+pub(crate) fn tokenize(document: &str) -> Vec<Token<'_>> {
+    let bytes = document.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        let kind = match c {
+            b' ' | b'\t' | b'\n' | b'\r' | b',' => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'"' if document[i..].starts_with("\"\"\"") => {
+                i += 3;
+                while i < bytes.len() && !document[i..].starts_with("\"\"\"") {
+                    i += if document[i..].starts_with("\\\"\"\"") { 4 } else { 1 };
+                }
+                i = (i + 3).min(bytes.len());
+                TokenKind::BlockString
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' && bytes[i] != b'\n' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+                TokenKind::String
+            }
+            b'.' if document[i..].starts_with("...") => {
+                i += 3;
+                TokenKind::Punctuator
+            }
+            b'-' | b'0'..=b'9' => {
+                i += 1;
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'.' | b'+' | b'-')) {
+                    i += 1;
+                }
+                TokenKind::Number
+            }
+            b'_' | b'a'..=b'z' | b'A'..=b'Z' => {
+                while i < bytes.len() && (bytes[i] == b'_' || bytes[i].is_ascii_alphanumeric()) {
+                    i += 1;
+                }
+                TokenKind::Name
+            }
+            _ => {
+                // Punctuators are single ASCII characters; skip over any other character whole.
+                i += document[i..].chars().next().map_or(1, char::len_utf8);
+                TokenKind::Punctuator
+            }
+        };
+        tokens.push(Token { kind, text: &document[start..i], start, end: i });
+    }
+
+    tokens
+}
+
+/// Returns the `(name, source)` of every top-level fragment definition in the document.
+pub(crate) fn fragment_definitions(document: &str) -> Vec<(String, String)> {
+    let tokens = tokenize(document);
+    let mut fragments = Vec::new();
+    let mut depth = 0usize;
+    let mut current: Option<(String, usize)> = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match (token.kind, token.text) {
+            (TokenKind::Name, "fragment") if depth == 0 && current.is_none() => {
+                if let Some(name) = tokens.get(i + 1).filter(|t| t.kind == TokenKind::Name) {
+                    current = Some((name.text.to_string(), token.start));
+                }
+            }
+            (TokenKind::Punctuator, "{") => depth += 1,
+            (TokenKind::Punctuator, "}") => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    if let Some((name, start)) = current.take() {
+                        fragments.push((name, document[start..token.end].to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fragments
+}
+
+/// Removes insignificant whitespace, commas and comments from the document.
+///
+/// Tokens are kept verbatim, so strings, block strings, directives such as
+/// `@include(if: $x)` and type conditions such as `... on User` are unchanged; a
+/// space is only kept between two names or numbers, where it is significant.
+pub(crate) fn minify(document: &str) -> String {
+    let mut minified = String::with_capacity(document.len());
+    let mut previous: Option<TokenKind> = None;
+
+    for token in tokenize(document) {
+        let is_word = matches!(token.kind, TokenKind::Name | TokenKind::Number);
+        if is_word && matches!(previous, Some(TokenKind::Name | TokenKind::Number)) {
+            minified.push(' ');
+        }
+        minified.push_str(token.text);
+        previous = Some(token.kind);
+    }
+
+    minified
+}
+
+/// The declared type of an operation variable, e.g. `[Int!]!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum VariableType {
+    Named { name: String, non_null: bool },
+    List { item: Box<VariableType>, non_null: bool },
+}
+
+impl VariableType {
+    pub(crate) fn is_non_null(&self) -> bool {
+        match self {
+            VariableType::Named { non_null, .. } | VariableType::List { non_null, .. } => *non_null,
+        }
+    }
+}
+
+impl fmt::Display for VariableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableType::Named { name, .. } => write!(f, "{}", name)?,
+            VariableType::List { item, .. } => write!(f, "[{}]", item)?,
+        }
+        if self.is_non_null() {
+            write!(f, "!")?;
+        }
+        Ok(())
+    }
+}
+
+/// A variable declared by an operation, e.g. `$count: Int! = 10`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VariableDefinition {
+    pub name: String,
+    pub variable_type: VariableType,
+    pub has_default: bool,
+}
+
+/// A selection within a selection set. Arguments and directives are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    Field { alias: Option<String>, name: String, selection_set: Vec<Selection> },
+    FragmentSpread(String),
+    InlineFragment { type_condition: Option<String>, selection_set: Vec<Selection> },
+}
+
+/// The kind of an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationType {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+/// An operation of a document; anonymous `{ ... }` operations are queries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationDefinition {
+    pub operation_type: OperationType,
+    pub name: Option<String>,
+    pub selection_set: Vec<Selection>,
+}
+
+/// A named fragment of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FragmentDefinition {
+    pub name: String,
+    pub type_condition: String,
+    pub selection_set: Vec<Selection>,
+}
+
+/// The operations and fragments of a document, keyed by fragment name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document {
+    pub operations: Vec<OperationDefinition>,
+    pub fragments: HashMap<String, FragmentDefinition>,
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(document: &'a str) -> Self {
+        Self { tokens: tokenize(document), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.pos)
+    }
+
+    fn is(&self, text: &str) -> bool {
+        self.peek().is_some_and(|t| t.kind == TokenKind::Punctuator && t.text == text)
+    }
+
+    fn is_name(&self) -> bool {
+        self.peek().is_some_and(|t| t.kind == TokenKind::Name)
+    }
+
+    /// Consumes the current token if it is a name.
+    fn name(&mut self) -> Option<String> {
+        let name = self.peek().filter(|t| t.kind == TokenKind::Name).map(|t| t.text.to_string());
+        if name.is_some() {
+            self.pos += 1;
+        }
+        name
+    }
+
+    /// Skips a balanced `open ... close` group; the current token must be `open`.
+    fn skip_balanced(&mut self, open: &str, close: &str) {
+        let mut depth = 0;
+        while let Some(&token) = self.peek() {
+            let text = token.text;
+            self.pos += 1;
+            if token.kind == TokenKind::Punctuator && text == open {
+                depth += 1;
+            } else if token.kind == TokenKind::Punctuator && text == close {
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn skip_directives(&mut self) {
+        while self.is("@") {
+            self.pos += 2;
+            if self.is("(") {
+                self.skip_balanced("(", ")");
+            }
+        }
+    }
+
+    /// Advances to the next top-level `{`, skipping argument and variable lists.
+    fn skip_to_selection_set(&mut self) -> bool {
+        while self.peek().is_some() {
+            if self.is("{") {
+                return true;
+            }
+            if self.is("(") {
+                self.skip_balanced("(", ")");
+            } else {
+                self.pos += 1;
+            }
+        }
+        false
+    }
+
+    /// Parses a selection set; the current token must be `{`.
+    fn selection_set(&mut self) -> Vec<Selection> {
+        self.pos += 1;
+        let mut selections = Vec::new();
+
+        while self.peek().is_some() {
+            if self.is("}") {
+                self.pos += 1;
+                break;
+            }
+            if self.is("...") {
+                self.pos += 1;
+                match self.peek().copied() {
+                    Some(t) if t.kind == TokenKind::Name && t.text != "on" => {
+                        selections.push(Selection::FragmentSpread(t.text.to_string()));
+                        self.pos += 1;
+                        self.skip_directives();
+                    }
+                    _ => {
+                        let mut type_condition = None;
+                        if self.peek().is_some_and(|t| t.text == "on") {
+                            self.pos += 1;
+                            type_condition = self.name();
+                        }
+                        self.skip_directives();
+                        if self.is("{") {
+                            selections.push(Selection::InlineFragment { type_condition, selection_set: self.selection_set() });
+                        }
+                    }
+                }
+            } else if let Some(mut name) = self.name() {
+                let mut alias = None;
+                if self.is(":") {
+                    self.pos += 1;
+                    if let Some(field) = self.name() {
+                        alias = Some(std::mem::replace(&mut name, field));
+                    }
+                }
+                if self.is("(") {
+                    self.skip_balanced("(", ")");
+                }
+                self.skip_directives();
+                let selection_set = if self.is("{") { self.selection_set() } else { Vec::new() };
+                selections.push(Selection::Field { alias, name, selection_set });
+            } else {
+                self.pos += 1;
+            }
+        }
+
+        selections
+    }
+
+    fn variable_type(&mut self) -> VariableType {
+        let variable_type = if self.is("[") {
+            self.pos += 1;
+            let item = Box::new(self.variable_type());
+            if self.is("]") {
+                self.pos += 1;
+            }
+            VariableType::List { item, non_null: false }
+        } else {
+            let name = self.peek().map(|t| t.text.to_string()).unwrap_or_default();
+            self.pos += 1;
+            VariableType::Named { name, non_null: false }
+        };
+
+        if !self.is("!") {
+            return variable_type;
+        }
+        self.pos += 1;
+        match variable_type {
+            VariableType::Named { name, .. } => VariableType::Named { name, non_null: true },
+            VariableType::List { item, .. } => VariableType::List { item, non_null: true },
+        }
+    }
+
+    /// Parses a variable definition list; the current token must be `(`.
+    fn variable_list(&mut self, definitions: &mut Vec<VariableDefinition>) {
+        self.pos += 1;
+        while self.peek().is_some() {
+            if self.is(")") {
+                self.pos += 1;
+                break;
+            }
+            if !self.is("$") {
+                self.pos += 1;
+                continue;
+            }
+            let name = self.tokens.get(self.pos + 1).map(|t| t.text.to_string()).unwrap_or_default();
+            self.pos += 2;
+            if self.is(":") {
+                self.pos += 1;
+            }
+            let variable_type = self.variable_type();
+            let has_default = self.is("=");
+            if has_default {
+                self.pos += 1;
+                if self.is("{") {
+                    self.skip_balanced("{", "}");
+                } else if self.is("[") {
+                    self.skip_balanced("[", "]");
+                } else {
+                    self.pos += 1;
+                }
+            }
+            self.skip_directives();
+            definitions.push(VariableDefinition { name, variable_type, has_default });
+        }
+    }
+
+    /// Parses the variable definitions of every operation in the document.
+    fn variable_definitions(mut self) -> Vec<VariableDefinition> {
+        let mut definitions = Vec::new();
+
+        while let Some(&token) = self.peek() {
+            if token.kind == TokenKind::Name && matches!(token.text, "query" | "mutation" | "subscription") {
+                self.pos += 1;
+                if self.is_name() {
+                    self.pos += 1;
+                }
+                if self.is("(") {
+                    self.variable_list(&mut definitions);
+                }
+            } else if self.is("{") {
+                self.skip_balanced("{", "}");
+            } else {
+                self.pos += 1;
+            }
+        }
+
+        definitions
+    }
+
+    /// Parses the operations and fragments of the document.
+    fn document(mut self) -> Document {
+        let mut document = Document::default();
+
+        while let Some(&token) = self.peek() {
+            let operation_type = match (token.kind, token.text) {
+                (TokenKind::Name, "query") => Some(OperationType::Query),
+                (TokenKind::Name, "mutation") => Some(OperationType::Mutation),
+                (TokenKind::Name, "subscription") => Some(OperationType::Subscription),
+                _ => None,
+            };
+            if token.kind == TokenKind::Name && token.text == "fragment" {
+                self.pos += 1;
+                let name = self.name().unwrap_or_default();
+                let type_condition = if self.peek().is_some_and(|t| t.text == "on") {
+                    self.pos += 1;
+                    self.name().unwrap_or_default()
+                } else {
+                    String::new()
+                };
+                if self.skip_to_selection_set() {
+                    let selection_set = self.selection_set();
+                    document.fragments.insert(name.clone(), FragmentDefinition { name, type_condition, selection_set });
+                }
+            } else if self.is("{") {
+                let selection_set = self.selection_set();
+                document.operations.push(OperationDefinition { operation_type: OperationType::Query, name: None, selection_set });
+            } else if let Some(operation_type) = operation_type {
+                self.pos += 1;
+                let name = self.name();
+                if self.skip_to_selection_set() {
+                    let selection_set = self.selection_set();
+                    document.operations.push(OperationDefinition { operation_type, name, selection_set });
+                }
+            } else if token.kind == TokenKind::Name {
+                // Not an executable definition; skip it whole.
+                if self.skip_to_selection_set() {
+                    self.skip_balanced("{", "}");
+                }
+            } else {
+                self.pos += 1;
+            }
+        }
+
+        document
+    }
+}
+
+/// Parses the operations and fragments of a document.
+///
+/// Like the other helpers, this does not validate the document: malformed parts are
+/// skipped rather than reported.
+///
+/// # Arguments
+///
+/// * `document` - The GraphQL document.
+pub fn parse(document: &str) -> Document {
+    Parser::new(document).document()
+}
+
+fn selection_depth(selections: &[Selection], fragments: &HashMap<String, FragmentDefinition>, visiting: &mut HashSet<String>) -> usize {
+    selections
+        .iter()
+        .map(|selection| match selection {
+            Selection::Field { selection_set, .. } => 1 + selection_depth(selection_set, fragments, visiting),
+            Selection::InlineFragment { selection_set, .. } => selection_depth(selection_set, fragments, visiting),
+            Selection::FragmentSpread(name) => match fragments.get(name) {
+                // A fragment cycle is invalid GraphQL; stop rather than recurse forever.
+                Some(fragment) if visiting.insert(name.clone()) => {
+                    let depth = selection_depth(&fragment.selection_set, fragments, visiting);
+                    visiting.remove(name);
+                    depth
+                }
+                _ => 0,
+            },
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the maximum nesting depth of fields across the operations of the document.
+///
+/// `{ viewer { login } }` has a depth of 2. Fragment spreads and inline fragments
+/// count as the fields they contain, at the level they are spread.
+pub(crate) fn max_depth(document: &str) -> usize {
+    let document = parse(document);
+    document
+        .operations
+        .iter()
+        .map(|operation| selection_depth(&operation.selection_set, &document.fragments, &mut HashSet::new()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns `true` if every operation of the document is a query, so that sending it
+/// has no side effects. Anonymous `{ ... }` operations are queries.
+pub(crate) fn is_query_only(document: &str) -> bool {
+    let mut depth = 0usize;
+    let mut at_definition = true;
+    for token in tokenize(document) {
+        match token.text {
+            "{" | "(" => depth += 1,
+            "}" | ")" => depth = depth.saturating_sub(1),
+            "mutation" | "subscription" if depth == 0 && at_definition => return false,
+            _ => {}
+        }
+        // Only the first token of a top-level definition names its kind.
+        at_definition = depth == 0 && token.text == "}";
+    }
+    true
+}
+
+/// Returns the variables declared by the operations of the document.
+pub(crate) fn variable_definitions(document: &str) -> Vec<VariableDefinition> {
+    Parser::new(document).variable_definitions()
+}
+
+fn value_matches(variable_type: &VariableType, value: &serde_json::Value) -> bool {
+    use serde_json::Value;
+
+    match (variable_type, value) {
+        (_, Value::Null) => !variable_type.is_non_null(),
+        (VariableType::List { item, .. }, Value::Array(items)) => items.iter().all(|v| value_matches(item, v)),
+        // A single value is coerced to a list of one.
+        (VariableType::List { item, .. }, value) => value_matches(item, value),
+        (VariableType::Named { name, .. }, value) => match name.as_str() {
+            "Int" => value.is_i64() || value.is_u64(),
+            "Float" => value.is_number(),
+            "String" => value.is_string(),
+            "Boolean" => value.is_boolean(),
+            "ID" => value.is_string() || value.is_i64() || value.is_u64(),
+            // Enums, input objects and custom scalars cannot be checked without the schema.
+            _ => true,
+        },
+    }
+}
+
+fn json_type(value: &serde_json::Value) -> &'static str {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "float",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Checks the JSON types of `variables` against the variables declared by the document,
+/// returning a description of every mismatch.
+///
+/// Only the built-in scalars can be checked; enums, input objects and custom scalars
+/// accept any value.
+pub(crate) fn validate_variables<'v, F>(document: &str, variable: F) -> Vec<String>
+where
+    F: Fn(&str) -> Option<&'v serde_json::Value>,
+{
+    let mut errors = Vec::new();
+    for definition in variable_definitions(document) {
+        match variable(&definition.name) {
+            Some(value) if !value_matches(&definition.variable_type, value) => errors.push(format!(
+                "Variable ${} expects {}, got {}",
+                definition.name,
+                definition.variable_type,
+                json_type(value)
+            )),
+            None if definition.variable_type.is_non_null() && !definition.has_default => {
+                errors.push(format!("Variable ${} of type {} is required", definition.name, definition.variable_type));
+            }
+            _ => {}
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_skips_strings_and_comments() {
+        let document = "query { # comment with { brace\n  user(bio: \"a { b\", note: \"\"\"x } \\\"\"\" y\"\"\") { ...F } }";
+        let punctuators: Vec<&str> = tokenize(document)
+            .into_iter()
+            .filter(|t| t.kind == TokenKind::Punctuator)
+            .map(|t| t.text)
+            .collect();
+
+        assert_eq!(punctuators, vec!["{", "(", ":", ":", ")", "{", "...", "}", "}"]);
+    }
+
+    #[test]
+    fn test_fragment_definitions() {
+        let document = "fragment A on User { id friends { ...B } }\n\nfragment B on User { name }";
+        let fragments = fragment_definitions(document);
+
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(fragments[0], ("A".to_string(), "fragment A on User { id friends { ...B } }".to_string()));
+        assert_eq!(fragments[1], ("B".to_string(), "fragment B on User { name }".to_string()));
+    }
+
+    #[test]
+    fn test_max_depth() {
+        assert_eq!(max_depth("{ viewer { login } }"), 2);
+        assert_eq!(max_depth("query Q($f: Filter = { a: { b: 1 } }) { a(x: { y: { z: 1 } }) @include(if: true) { b } c }"), 2);
+
+        let document = "
+            query { viewer { ...UserFields ... on User { a { b { c } } } } }
+            fragment UserFields on User { friends { ...Leaf } }
+            fragment Leaf on User { login }
+        ";
+        assert_eq!(max_depth(document), 4);
+        assert_eq!(max_depth("{ a { ...A } } fragment A on T { ...A b }"), 2);
+    }
+
+    #[test]
+    fn test_parse() {
+        let document = parse("
+            mutation Save($id: ID!) @log { me: user(id: $id) @include(if: true) { ...F ... on Admin { level } } }
+            fragment F on User { name }
+            { ... @skip(if: false) { id } }
+        ");
+
+        let field = |alias: Option<&str>, name: &str, selection_set| Selection::Field {
+            alias: alias.map(str::to_string),
+            name: name.to_string(),
+            selection_set,
+        };
+        assert_eq!(
+            document.operations,
+            vec![
+                OperationDefinition {
+                    operation_type: OperationType::Mutation,
+                    name: Some("Save".to_string()),
+                    selection_set: vec![field(
+                        Some("me"),
+                        "user",
+                        vec![
+                            Selection::FragmentSpread("F".to_string()),
+                            Selection::InlineFragment { type_condition: Some("Admin".to_string()), selection_set: vec![field(None, "level", vec![])] },
+                        ]
+                    )],
+                },
+                OperationDefinition {
+                    operation_type: OperationType::Query,
+                    name: None,
+                    selection_set: vec![Selection::InlineFragment { type_condition: None, selection_set: vec![field(None, "id", vec![])] }],
+                },
+            ]
+        );
+        assert_eq!(document.fragments["F"].type_condition, "User");
+        assert_eq!(document.fragments["F"].selection_set, vec![field(None, "name", vec![])]);
+    }
+
+    #[test]
+    fn test_variable_definitions() {
+        let document = "query Q($count: Int! = 10, $ids: [ID!], $filter: Filter = { a: [1] } @deprecated) { a(x: { y: $count }) }";
+        let definitions = variable_definitions(document);
+
+        let declared: Vec<(String, String, bool)> = definitions
+            .into_iter()
+            .map(|d| (d.name, d.variable_type.to_string(), d.has_default))
+            .collect();
+        assert_eq!(
+            declared,
+            vec![
+                ("count".to_string(), "Int!".to_string(), true),
+                ("ids".to_string(), "[ID!]".to_string(), false),
+                ("filter".to_string(), "Filter".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_variables() {
+        let document = "query($count: Int!, $ids: [ID!], $name: String, $ratio: Float = 1.0, $on: Boolean!, $order: Order) { a }";
+        let variables = serde_json::json!({ "count": "10", "ids": [1, "2", null], "ratio": 1, "order": 3 });
+
+        let errors = validate_variables(document, |name| variables.get(name));
+        assert_eq!(
+            errors,
+            vec![
+                "Variable $count expects Int!, got string".to_string(),
+                "Variable $ids expects [ID!], got array".to_string(),
+                "Variable $on of type Boolean! is required".to_string(),
+            ]
+        );
+
+        let variables = serde_json::json!({ "count": 10, "ids": "1", "name": null, "on": true });
+        assert!(validate_variables(document, |name| variables.get(name)).is_empty());
+    }
+
+    #[test]
+    fn test_minify_preserves_directives_and_fragments() {
+        let document = "
+            query User($id: ID!, $withFriends: Boolean!, $skipBio: Boolean = false) {
+                # The current user
+                user(id: $id, tags: [1, 2]) {
+                    ... on User @include(if: $withFriends) { friends { name } }
+                    bio @skip(if: $skipBio)
+                    greeting(text: \"hello,  world # not a comment\")
+                    ...UserFields
+                }
+            }
+        ";
+
+        assert_eq!(
+            minify(document),
+            "query User($id:ID!$withFriends:Boolean!$skipBio:Boolean=false){user(id:$id tags:[1 2]){...on User@include(if:$withFriends){friends{name}}bio@skip(if:$skipBio)greeting(text:\"hello,  world # not a comment\")...UserFields}}"
+        );
+    }
+}
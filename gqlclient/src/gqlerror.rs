@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::QueryTiming;
+
 #[derive(Debug)]
 pub struct GraphQLClientError {
     pub errors: Vec<GQLError>,
@@ -14,7 +20,346 @@ impl fmt::Display for GraphQLClientError {
     }
 } 
 
-#[derive(Deserialize, Serialize, Debug)]
+impl GraphQLClientError {
+    /// Formats the errors for humans, one per line with their path and location, e.g.
+    ///
+    /// ```text
+    /// error: Field "nme" does not exist
+    ///   at user.friends[0] (line 3, col 7)
+    /// ```
+    pub fn to_pretty_string(&self) -> String {
+        self.errors.iter().map(GQLError::to_pretty_string).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Groups the errors by their `extensions.code`, e.g. to refresh credentials on
+    /// `UNAUTHENTICATED`. Errors without a code are grouped under `UNKNOWN`.
+    pub fn group_by_code(&self) -> HashMap<String, Vec<&GQLError>> {
+        let mut groups: HashMap<String, Vec<&GQLError>> = HashMap::new();
+        for error in &self.errors {
+            groups.entry(error.code().unwrap_or("UNKNOWN").to_string()).or_default().push(error);
+        }
+        groups
+    }
+
+    /// Summarizes the error counts per code, most frequent first, e.g. `3 UNAUTHENTICATED, 1 BAD_USER_INPUT`.
+    pub fn code_summary(&self) -> String {
+        let mut counts: Vec<_> = self.group_by_code().into_iter().map(|(code, errors)| (errors.len(), code)).collect();
+        counts.sort_by(|(a_count, a_code), (b_count, b_code)| b_count.cmp(a_count).then_with(|| a_code.cmp(b_code)));
+        counts.iter().map(|(count, code)| format!("{} {}", count, code)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// A position in the query document, as reported in `GQLError::locations`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GQLError {
     message: String,
-}
\ No newline at end of file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    locations: Option<Vec<Location>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extensions: Option<serde_json::Value>,
+}
+
+impl GQLError {
+    /// Returns the error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the locations in the query document the error refers to.
+    pub fn locations(&self) -> &[Location] {
+        self.locations.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the path of the response field the error refers to, made of field
+    /// names and list indices.
+    pub fn path(&self) -> &[serde_json::Value] {
+        self.path.as_deref().unwrap_or_default()
+    }
+
+    /// Formats the error as `error: <message>`, followed by `  at <path> (line X, col Y)`
+    /// when the server reported a path or locations.
+    pub fn to_pretty_string(&self) -> String {
+        let mut pretty = format!("error: {}", self.message);
+
+        let mut path = String::new();
+        for segment in self.path() {
+            match segment {
+                serde_json::Value::Number(index) => path.push_str(&format!("[{}]", index)),
+                serde_json::Value::String(name) if path.is_empty() => path.push_str(name),
+                segment => path.push_str(&format!(".{}", segment.as_str().unwrap_or_default())),
+            }
+        }
+        let locations = self
+            .locations()
+            .iter()
+            .map(|location| format!("line {}, col {}", location.line, location.column))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        match (path.is_empty(), locations.is_empty()) {
+            (true, true) => {}
+            (false, true) => pretty.push_str(&format!("\n  at {}", path)),
+            (true, false) => pretty.push_str(&format!("\n  at {}", locations)),
+            (false, false) => pretty.push_str(&format!("\n  at {} ({})", path, locations)),
+        }
+        pretty
+    }
+
+    /// Returns the server-specific `extensions` of the error, if any.
+    pub fn extensions(&self) -> Option<&serde_json::Value> {
+        self.extensions.as_ref()
+    }
+
+    /// Returns `extensions.code`, if the server provided one.
+    pub fn code(&self) -> Option<&str> {
+        self.extensions.as_ref()?.get("code")?.as_str()
+    }
+}
+
+/// A failure to exchange an HTTP request with the GraphQL endpoint.
+///
+/// Transport failures are categorized so callers can give actionable guidance,
+/// e.g. "check the hostname" for DNS failures versus "the server rejected TLS".
+#[derive(Debug)]
+pub enum RequestError {
+    /// The endpoint host name could not be resolved.
+    Dns(reqwest::Error),
+    /// The connection was refused or could not be established.
+    Connect(reqwest::Error),
+    /// The TLS handshake or certificate validation failed.
+    Tls(reqwest::Error),
+    /// The request timed out.
+    Timeout(reqwest::Error),
+    /// The server redirected the request and redirects are not followed.
+    UnexpectedRedirect { status: reqwest::StatusCode, location: Option<String> },
+    /// The endpoint uses plain `http://` and insecure HTTP was not allowed.
+    InsecureHttp { url: String },
+    /// Any other HTTP-level failure.
+    Http(reqwest::Error),
+    /// The request, including its retries, did not complete within `GQLClient::overall_deadline`.
+    DeadlineExceeded { deadline: Duration },
+    /// The request was cancelled through the token given to `GQLClient::with_cancellation`.
+    Cancelled,
+    /// The HTTP client could not be built from the configured redirect policy, timeout or socket.
+    ClientBuild(Arc<reqwest::Error>),
+}
+
+impl RequestError {
+    /// Returns `true` if the endpoint could not be reached at all.
+    pub(crate) fn is_unavailable(&self) -> bool {
+        matches!(self, RequestError::Dns(_) | RequestError::Connect(_) | RequestError::Tls(_) | RequestError::Timeout(_))
+    }
+
+    fn source_chain_contains(err: &reqwest::Error, needles: &[&str]) -> bool {
+        let mut source: Option<&(dyn Error + 'static)> = err.source();
+        while let Some(current) = source {
+            let message = current.to_string().to_ascii_lowercase();
+            if needles.iter().any(|needle| message.contains(needle)) {
+                return true;
+            }
+            source = current.source();
+        }
+        false
+    }
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            RequestError::Timeout(err)
+        } else if err.is_connect() {
+            if Self::source_chain_contains(&err, &["dns error", "failed to lookup address"]) {
+                RequestError::Dns(err)
+            } else if Self::source_chain_contains(&err, &["tls", "ssl", "certificate", "handshake"]) {
+                RequestError::Tls(err)
+            } else {
+                RequestError::Connect(err)
+            }
+        } else {
+            RequestError::Http(err)
+        }
+    }
+}
+
+impl Error for RequestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RequestError::Dns(err)
+            | RequestError::Connect(err)
+            | RequestError::Tls(err)
+            | RequestError::Timeout(err)
+            | RequestError::Http(err) => Some(err),
+            RequestError::ClientBuild(err) => Some(err.as_ref()),
+            RequestError::UnexpectedRedirect { .. } | RequestError::InsecureHttp { .. } | RequestError::DeadlineExceeded { .. } | RequestError::Cancelled => None,
+        }
+    }
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::Dns(err) => write!(f, "Could not resolve the endpoint host, check the hostname: {}", err),
+            RequestError::Connect(err) => write!(f, "Could not connect to the endpoint, check that the server is running: {}", err),
+            RequestError::Tls(err) => write!(f, "The TLS connection to the endpoint failed: {}", err),
+            RequestError::Timeout(err) => write!(f, "The request to the endpoint timed out: {}", err),
+            RequestError::UnexpectedRedirect { status, location } => {
+                write!(f, "Unexpected redirect ({}) to {}", status, location.as_deref().unwrap_or("<unknown>"))
+            }
+            RequestError::InsecureHttp { url } => write!(
+                f,
+                "Refusing to send credentials in cleartext to {}; use https or allow_insecure_http(true)",
+                url
+            ),
+            RequestError::Http(err) => write!(f, "HTTP request failed: {}", err),
+            RequestError::DeadlineExceeded { deadline } => write!(f, "The request did not complete within its deadline of {:?}", deadline),
+            RequestError::Cancelled => write!(f, "The request was cancelled"),
+            RequestError::ClientBuild(err) => write!(f, "The HTTP client could not be built: {}", err),
+        }
+    }
+}
+
+/// Everything that was wrong with a response received in strict mode.
+#[derive(Debug)]
+pub struct StrictModeError {
+    /// The HTTP status, if it was not a 2xx.
+    pub status: Option<reqwest::StatusCode>,
+    /// The GraphQL errors returned by the server.
+    pub errors: Vec<GQLError>,
+    /// Whether the response had no `data`, or `null` data.
+    pub missing_data: bool,
+}
+
+impl Error for StrictModeError {}
+
+impl fmt::Display for StrictModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut problems = Vec::new();
+        if let Some(status) = self.status {
+            problems.push(format!("HTTP {}", status));
+        }
+        if !self.errors.is_empty() {
+            let messages: Vec<&str> = self.errors.iter().map(|e| e.message.as_str()).collect();
+            problems.push(format!("GraphQL errors: {}", messages.join(", ")));
+        }
+        if self.missing_data {
+            problems.push("response has no data".to_string());
+        }
+        write!(f, "Strict mode: {}", problems.join("; "))
+    }
+}
+
+/// A response that could not be deserialized into the requested type, with the body
+/// the server actually returned.
+#[derive(Debug)]
+pub struct DeserializeError {
+    /// The underlying serde error.
+    pub source: serde_json::Error,
+    /// The response body, truncated to `GQLClient::with_error_body_limit` bytes.
+    pub body: String,
+    /// Whether `body` was truncated.
+    pub truncated: bool,
+}
+
+impl DeserializeError {
+    pub(crate) fn new(source: serde_json::Error, body: &str, limit: usize) -> Self {
+        let mut end = limit.min(body.len());
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        Self {
+            source,
+            body: body[..end].to_string(),
+            truncated: end < body.len(),
+        }
+    }
+}
+
+impl Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ellipsis = if self.truncated { "..." } else { "" };
+        write!(f, "Failed to deserialize the response: {}; body: {}{}", self.source, self.body, ellipsis)
+    }
+}
+
+/// An error returned by `GQLClient::run_query_timed`, with the timing captured up to the failure.
+#[derive(Debug)]
+pub struct TimedError {
+    /// How long the query ran before it failed.
+    pub timing: QueryTiming,
+    /// The underlying error.
+    pub error: Box<dyn Error>,
+}
+
+impl Error for TimedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+impl fmt::Display for TimedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_by_code() {
+        let errors: Vec<GQLError> = serde_json::from_str(
+            r#"[
+                {"message":"Not signed in","extensions":{"code":"UNAUTHENTICATED"}},
+                {"message":"Bad id","extensions":{"code":"BAD_USER_INPUT"}},
+                {"message":"Token expired","extensions":{"code":"UNAUTHENTICATED"}},
+                {"message":"Internal error"}
+            ]"#,
+        )
+        .unwrap();
+        let error = GraphQLClientError { errors };
+
+        let groups = error.group_by_code();
+        let messages: Vec<_> = groups["UNAUTHENTICATED"].iter().map(|error| error.message()).collect();
+        assert_eq!(messages, ["Not signed in", "Token expired"]);
+        assert_eq!(groups["UNKNOWN"].len(), 1);
+        assert_eq!(error.code_summary(), "2 UNAUTHENTICATED, 1 BAD_USER_INPUT, 1 UNKNOWN");
+    }
+
+    #[test]
+    fn test_to_pretty_string() {
+        let errors: Vec<GQLError> = serde_json::from_str(
+            r#"[
+                {"message":"Field \"nme\" does not exist","locations":[{"line":3,"column":7}],"path":["user","friends",0]},
+                {"message":"Not found","path":["repository"]},
+                {"message":"Syntax error","locations":[{"line":1,"column":2},{"line":4,"column":1}]},
+                {"message":"Internal error"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(errors[0].locations(), [Location { line: 3, column: 7 }]);
+
+        assert_eq!(
+            GraphQLClientError { errors }.to_pretty_string(),
+            "error: Field \"nme\" does not exist\n  at user.friends[0] (line 3, col 7)\n\
+             error: Not found\n  at repository\n\
+             error: Syntax error\n  at line 1, col 2; line 4, col 1\n\
+             error: Internal error"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+/// A `login`/`password` pair from a netrc file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NetrcEntry {
+    pub(crate) login: Option<String>,
+    pub(crate) password: Option<String>,
+}
+
+/// Finds the entry for `host` in the contents of a netrc file, falling back to the
+/// `default` entry. Macro definitions (`macdef`) are skipped.
+pub(crate) fn lookup(contents: &str, host: &str) -> Option<NetrcEntry> {
+    let mut lines = Vec::new();
+    let mut in_macro = false;
+    for line in contents.lines() {
+        if in_macro {
+            in_macro = !line.trim().is_empty();
+        } else if line.split_whitespace().next() == Some("macdef") {
+            in_macro = true;
+        } else {
+            lines.push(line);
+        }
+    }
+
+    let mut tokens = lines.iter().flat_map(|line| line.split_whitespace());
+    let mut found = None;
+    let mut default = None;
+    // The entry the current tokens belong to: `Some(true)` for `host`, `Some(false)` for `default`.
+    let mut current = None;
+    while let Some(token) = tokens.next() {
+        let entry = match current {
+            Some(true) => found.as_mut(),
+            Some(false) => default.as_mut(),
+            None => None,
+        };
+        match token {
+            "machine" => {
+                current = None;
+                if tokens.next() == Some(host) && found.is_none() {
+                    found = Some(NetrcEntry { login: None, password: None });
+                    current = Some(true);
+                }
+            }
+            "default" => {
+                current = None;
+                if default.is_none() {
+                    default = Some(NetrcEntry { login: None, password: None });
+                    current = Some(false);
+                }
+            }
+            "login" => {
+                let value = tokens.next().map(str::to_string);
+                if let Some(entry) = entry {
+                    entry.login = value;
+                }
+            }
+            "password" => {
+                let value = tokens.next().map(str::to_string);
+                if let Some(entry) = entry {
+                    entry.password = value;
+                }
+            }
+            "account" => {
+                tokens.next();
+            }
+            _ => {}
+        }
+    }
+    found.or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let contents = "machine other.example.com login someone password elsewhere
+macdef init
+machine api.example.com password hidden
+
+machine api.example.com
+    login svc
+    password s3cret
+default password fallback
+";
+        let entry = |login: Option<&str>, password: &str| NetrcEntry { login: login.map(str::to_string), password: Some(password.to_string()) };
+
+        assert_eq!(lookup(contents, "api.example.com"), Some(entry(Some("svc"), "s3cret")));
+        assert_eq!(lookup(contents, "unknown.example.com"), Some(entry(None, "fallback")));
+        assert_eq!(lookup("machine a login b password c", "d"), None);
+    }
+}
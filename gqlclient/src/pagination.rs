@@ -0,0 +1,86 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// The variables of a Relay-style connection: `first`/`after` to page forward or
+/// `last`/`before` to page backward.
+///
+/// ```
+/// use gqlclient::{Pagination, QueryBuilder};
+///
+/// let mut query_builder = QueryBuilder::new(
+///     "query ($first: Int, $after: String) { repositories(first: $first, after: $after) { nodes { name } } }",
+/// );
+/// query_builder.set_pagination(&Pagination::default().forward(50, Some("Y3Vyc29yOjUw"))).unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pagination {
+    first: Option<u64>,
+    after: Option<String>,
+    last: Option<u64>,
+    before: Option<String>,
+}
+
+impl Pagination {
+    /// Pages forward, returning the first `first` items after the `after` cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The number of items to return.
+    /// * `after` - The cursor to start after, or `None` for the first page.
+    pub fn forward(mut self, first: u64, after: Option<&str>) -> Self {
+        self.first = Some(first);
+        self.after = after.map(str::to_string);
+        self
+    }
+
+    /// Pages backward, returning the last `last` items before the `before` cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `last` - The number of items to return.
+    /// * `before` - The cursor to end before, or `None` for the last page.
+    pub fn backward(mut self, last: u64, before: Option<&str>) -> Self {
+        self.last = Some(last);
+        self.before = before.map(str::to_string);
+        self
+    }
+
+    /// Sets the variables of the chosen direction and clears those of the other one.
+    pub(crate) fn apply(&self, variables: &mut HashMap<String, Value>) -> Result<(), Box<dyn Error>> {
+        let (set, cleared) = match (self.first, self.last) {
+            (Some(_), Some(_)) => return Err("Pagination cannot mix forward (first/after) and backward (last/before) arguments".into()),
+            (Some(first), None) => ([("first", first.into()), ("after", self.after.clone().into())], ["last", "before"]),
+            (None, Some(last)) => ([("last", last.into()), ("before", self.before.clone().into())], ["first", "after"]),
+            (None, None) => return Err("Pagination needs either forward or backward arguments".into()),
+        };
+
+        for key in cleared {
+            variables.remove(key);
+        }
+        for (key, value) in set {
+            variables.insert(key.to_string(), value);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QueryBuilder;
+    use serde_json::json;
+
+    #[test]
+    fn test_set_pagination() {
+        let mut query_builder = QueryBuilder::new("query ($first: Int, $after: String, $last: Int, $before: String) { a }");
+        query_builder.set_pagination(&Pagination::default().backward(5, Some("b"))).unwrap();
+        query_builder.set_pagination(&Pagination::default().forward(10, None)).unwrap();
+        assert_eq!(query_builder.body()["variables"], json!({ "first": 10, "after": null }));
+
+        let mixed = Pagination::default().forward(10, None).backward(5, None);
+        let err = query_builder.set_pagination(&mixed).unwrap_err();
+        assert_eq!(err.to_string(), "Pagination cannot mix forward (first/after) and backward (last/before) arguments");
+        assert!(query_builder.set_pagination(&Pagination::default()).is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! Commonly used types, re-exported for convenience.
+//!
+//! ```no_run
+//! use gqlclient::prelude::*;
+//!
+//! let client = GQLClient::new("https://api.github.com/graphql");
+//! let query_builder = QueryBuilder::new("{ viewer { login } }");
+//! let response = client.run_query::<serde_json::Value>(&query_builder);
+//! ```
+
+pub use crate::{DeserializeError, GQLClient, GQLError, GraphQLClientError, Operation, Pagination, QueryBuilder, QueryOutcome, QueryTiming, RedirectPolicy, RequestError, RetryPolicy, ServerKind, StrictModeError, Subscription, TimedError};
@@ -0,0 +1,104 @@
+use reqwest::StatusCode;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::GQLError;
+
+/// A predicate deciding whether a GraphQL error is worth retrying.
+pub type GraphQLErrorPredicate = Arc<dyn Fn(&GQLError) -> bool + Send + Sync>;
+
+/// Controls how failed requests are retried.
+///
+/// Requests are retried when the endpoint cannot be reached or answers with
+/// HTTP 429, and optionally when the response carries a matching GraphQL error.
+/// The delay between attempts doubles, starting from `backoff`.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    pub(crate) max_retries: usize,
+    pub(crate) backoff: Duration,
+    pub(crate) retry_on_graphql_error: Option<GraphQLErrorPredicate>,
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("backoff", &self.backoff)
+            .field("retry_on_graphql_error", &self.retry_on_graphql_error.is_some())
+            .finish()
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a new `RetryPolicy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_retries` - The maximum number of retries after the first attempt.
+    /// * `backoff` - The delay before the first retry.
+    pub fn new(max_retries: usize, backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            backoff,
+            retry_on_graphql_error: None,
+        }
+    }
+
+    /// Also retries responses containing a GraphQL error matching the predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A function returning `true` for errors that should be retried.
+    pub fn retry_on_graphql_error<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&GQLError) -> bool + Send + Sync + 'static,
+    {
+        self.retry_on_graphql_error = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Also retries GraphQL-level rate limiting, signalled by `extensions.code == "RATE_LIMITED"`.
+    ///
+    /// APIs such as GitHub report rate limiting this way in a 200 response.
+    pub fn retry_on_rate_limited(self) -> Self {
+        self.retry_on_graphql_error(|error| error.code() == Some("RATE_LIMITED"))
+    }
+
+    pub(crate) fn delay(&self, attempt: usize) -> Duration {
+        self.backoff.saturating_mul(1 << attempt.min(16) as u32)
+    }
+
+    pub(crate) fn should_retry(&self, status: StatusCode, errors: &[GQLError]) -> bool {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return true;
+        }
+        match &self.retry_on_graphql_error {
+            Some(predicate) => errors.iter().any(|error| predicate(error)),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_retry() {
+        let rate_limited: GQLError =
+            serde_json::from_str(r#"{"message":"API rate limit exceeded","extensions":{"code":"RATE_LIMITED"}}"#).unwrap();
+        let other: GQLError = serde_json::from_str(r#"{"message":"Not found"}"#).unwrap();
+
+        let policy = RetryPolicy::new(3, Duration::from_millis(100));
+        assert!(policy.should_retry(StatusCode::TOO_MANY_REQUESTS, &[]));
+        assert!(!policy.should_retry(StatusCode::OK, std::slice::from_ref(&rate_limited)));
+
+        let policy = policy.retry_on_rate_limited();
+        assert!(policy.should_retry(StatusCode::OK, &[other.clone(), rate_limited]));
+        assert!(!policy.should_retry(StatusCode::OK, &[other]));
+
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+    }
+}
@@ -0,0 +1,68 @@
+use reqwest::header::HeaderMap;
+
+use crate::GQLError;
+
+/// A GraphQL server implementation, as recognized by `GQLClient::detect_server`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerKind {
+    Apollo,
+    Yoga,
+    Hasura,
+    PostGraphile,
+}
+
+/// The field the probe query selects, which no schema is expected to define.
+pub(crate) const PROBE_FIELD: &str = "__gqlrs_probe";
+
+/// Guesses the server implementation from the response to the probe query.
+///
+/// Headers are checked first, then the shape of the error reported for the unknown field.
+pub(crate) fn detect(headers: &HeaderMap, errors: &[GQLError]) -> Option<ServerKind> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).unwrap_or_default().to_ascii_lowercase();
+    let has_header_prefix = |prefix: &str| headers.keys().any(|name| name.as_str().starts_with(prefix));
+
+    let powered_by = header("x-powered-by");
+    if powered_by.contains("postgraphile") {
+        return Some(ServerKind::PostGraphile);
+    }
+    if powered_by.contains("yoga") || has_header_prefix("x-yoga") {
+        return Some(ServerKind::Yoga);
+    }
+    if header("server").contains("hasura") || has_header_prefix("x-hasura") {
+        return Some(ServerKind::Hasura);
+    }
+    if has_header_prefix("apollo") {
+        return Some(ServerKind::Apollo);
+    }
+
+    errors.iter().find_map(|error| match error.code() {
+        Some("GRAPHQL_VALIDATION_FAILED") => Some(ServerKind::Apollo),
+        Some("validation-failed") => Some(ServerKind::Hasura),
+        _ if error.message().contains("query_root") => Some(ServerKind::Hasura),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_detect() {
+        let error = |raw: &str| serde_json::from_str::<GQLError>(raw).unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(detect(&headers, &[]), None);
+        assert_eq!(
+            detect(&headers, &[error(r#"{"message":"Cannot query field","extensions":{"code":"GRAPHQL_VALIDATION_FAILED"}}"#)]),
+            Some(ServerKind::Apollo)
+        );
+        assert_eq!(
+            detect(&headers, &[error(r#"{"message":"field '__gqlrs_probe' not found in type: 'query_root'"}"#)]),
+            Some(ServerKind::Hasura)
+        );
+
+        headers.insert("x-powered-by", HeaderValue::from_static("PostGraphile"));
+        assert_eq!(detect(&headers, &[]), Some(ServerKind::PostGraphile));
+    }
+}
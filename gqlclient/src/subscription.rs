@@ -0,0 +1,194 @@
+use reqwest::blocking::Response;
+use reqwest::header::{HeaderValue, ACCEPT};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, BufRead, BufReader};
+use std::marker::PhantomData;
+use std::thread;
+use std::time::Duration;
+
+use crate::{GQLClient, RequestError, RetryPolicy};
+
+/// A GraphQL subscription streamed over server-sent events.
+///
+/// Each `next` event yields the `data` of one result; iteration ends when the server
+/// sends `complete`. With `with_reconnect`, a dropped stream is reopened with the id of
+/// the last event seen in `Last-Event-ID`, so a server supporting resumption can
+/// continue where it left off. A stream that fails mid-read yields the error once no
+/// reconnection attempt is left.
+///
+/// The stream is not subject to the client's request timeout; use `with_idle_timeout`
+/// to bound how long to wait for the next event.
+pub struct Subscription<'a, T> {
+    client: &'a GQLClient,
+    body: Value,
+    headers: HashMap<String, String>,
+    reconnect: Option<RetryPolicy>,
+    idle_timeout: Option<Duration>,
+    reader: Option<BufReader<Response>>,
+    last_event_id: Option<String>,
+    attempt: usize,
+    done: bool,
+    _marker: PhantomData<T>,
+}
+
+#[derive(Default)]
+struct Event {
+    id: Option<String>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl<'a, T: DeserializeOwned> Subscription<'a, T> {
+    pub(crate) fn new(client: &'a GQLClient, body: Value, headers: HashMap<String, String>) -> Self {
+        Self {
+            client,
+            body,
+            headers,
+            reconnect: None,
+            idle_timeout: None,
+            reader: None,
+            last_event_id: None,
+            attempt: 0,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reopens the stream when it drops or cannot be opened, waiting between attempts
+    /// as described by `policy`.
+    ///
+    /// The attempt count resets whenever an event is received.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The backoff and maximum number of reconnection attempts.
+    pub fn with_reconnect(mut self, policy: RetryPolicy) -> Self {
+        self.reconnect = Some(policy);
+        self
+    }
+
+    /// Fails the stream when no data arrives for `timeout`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait between reads.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the id of the last event received, sent as `Last-Event-ID` on reconnect.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    fn connect(&self) -> Result<BufReader<Response>, Box<dyn Error>> {
+        let url = self.client.endpoints.first().ok_or("No endpoint configured")?;
+        let mut request = self.client.build_request(url, &self.body, &self.headers)?.build()?;
+        request.headers_mut().insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+        if let Some(id) = &self.last_event_id {
+            request.headers_mut().insert("Last-Event-ID", HeaderValue::from_str(id)?);
+        }
+
+        let response = self.client.build_stream_client(self.idle_timeout)?.execute(request).map_err(RequestError::from)?;
+        if !response.status().is_success() {
+            return Err(format!("Subscription failed with HTTP {}", response.status()).into());
+        }
+        Ok(BufReader::new(response))
+    }
+
+    /// Reads the next event, or `None` when the server closes the stream.
+    fn read_event(reader: &mut BufReader<Response>) -> Result<Option<Event>, io::Error> {
+        let mut event = Event::default();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                if event.event.is_some() || !event.data.is_empty() {
+                    return Ok(Some(event));
+                }
+                continue;
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "id" => event.id = Some(value.to_string()),
+                "event" => event.event = Some(value.to_string()),
+                "data" => event.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Waits before the next reconnection attempt, or returns `false` if none is left.
+    fn backoff(&mut self) -> bool {
+        match &self.reconnect {
+            Some(policy) if self.attempt < policy.max_retries => {
+                thread::sleep(policy.delay(self.attempt));
+                self.attempt += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for Subscription<'_, T> {
+    type Item = Result<T, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let reader = match &mut self.reader {
+                Some(reader) => reader,
+                None => match self.connect() {
+                    Ok(reader) => self.reader.insert(reader),
+                    Err(err) => {
+                        if self.backoff() {
+                            continue;
+                        }
+                        self.done = true;
+                        return Some(Err(err));
+                    }
+                },
+            };
+
+            let event = match Self::read_event(reader) {
+                Ok(Some(event)) => event,
+                Ok(None) => {
+                    self.reader = None;
+                    if !self.backoff() {
+                        self.done = true;
+                    }
+                    continue;
+                }
+                Err(err) => {
+                    self.reader = None;
+                    if self.backoff() {
+                        continue;
+                    }
+                    self.done = true;
+                    return Some(Err(Box::new(err)));
+                }
+            };
+
+            self.attempt = 0;
+            if event.id.is_some() {
+                self.last_event_id = event.id;
+            }
+            match event.event.as_deref() {
+                Some("complete") => self.done = true,
+                Some("next") | None => return Some(self.client.parse_response(&event.data.join("\n"))),
+                Some(_) => {}
+            }
+        }
+        None
+    }
+}